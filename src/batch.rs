@@ -0,0 +1,227 @@
+//! `run <day> --inputs-dir <dir>`: one day against every input in a
+//! directory, such as one per team member, laid out as a matrix of input by
+//! part.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::bench::format_duration;
+use crate::input::InputSource;
+use crate::results::ResultCache;
+use crate::runner::{self, DayRun, Job, RunOptions, Status};
+use crate::solution::{Answer, Solution};
+use crate::verify::Answers;
+
+/// One input in the directory, and the name its answers are filed under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchInput {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// The inputs in `dir` for `day`, sorted by name: every file directly inside
+/// it, named by its stem, and every subdirectory holding a `dayN.txt`, named
+/// after the directory as with `--profile`. Hidden entries are skipped.
+pub fn inputs(dir: &Path, day: u8) -> io::Result<Vec<BatchInput>> {
+    let entries = fs::read_dir(dir).map_err(|err| io::Error::new(err.kind(), format!("{}: {}", dir.display(), err)))?;
+    let mut inputs = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let (Some(stem), Some(file_name)) = (path.file_stem(), path.file_name()) else {
+            continue;
+        };
+        if file_name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let (name, path) = if path.is_dir() {
+            (file_name, path.join(format!("day{}.txt", day)))
+        } else {
+            (stem, path.clone())
+        };
+        if path.is_file() {
+            inputs.push(BatchInput { name: name.to_string_lossy().into_owned(), path });
+        }
+    }
+    inputs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(inputs)
+}
+
+/// How one part, or a whole input, compares with the answers file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Outcome {
+    Pass,
+    /// No expected answer is recorded for this input.
+    Unknown,
+    Mismatch,
+    Error,
+}
+
+impl Outcome {
+    /// Whether the row should stand out.
+    pub fn is_problem(self) -> bool {
+        self >= Outcome::Mismatch
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Unknown => write!(f, "unknown"),
+            Outcome::Mismatch => write!(f, "MISMATCH"),
+            Outcome::Error => write!(f, "ERROR"),
+        }
+    }
+}
+
+/// One input's run, with the answers recorded for it.
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub input: String,
+    pub run: DayRun,
+    pub expected: [Option<String>; 2],
+}
+
+impl Row {
+    fn answer(&self, part: u8) -> &Option<Answer> {
+        if part == 1 {
+            &self.run.part1
+        } else {
+            &self.run.part2
+        }
+    }
+
+    pub fn part_outcome(&self, part: u8) -> Outcome {
+        match (self.answer(part), &self.expected[part as usize - 1]) {
+            (None, _) => Outcome::Error,
+            (Some(_), None) => Outcome::Unknown,
+            (Some(actual), Some(expected)) if actual.to_string() == *expected => Outcome::Pass,
+            (Some(_), Some(_)) => Outcome::Mismatch,
+        }
+    }
+
+    /// The worst of both parts, and an error if the run failed at all.
+    pub fn outcome(&self) -> Outcome {
+        match self.run.status {
+            Status::Ok => self.part_outcome(1).max(self.part_outcome(2)),
+            _ => Outcome::Error,
+        }
+    }
+}
+
+/// Runs `day` against every input in parallel, answering from `cache` where
+/// it can, and pairs each run with its expected answers. The rows come back in the order of `inputs`.
+pub fn run(
+    day: u8,
+    new: fn() -> Box<dyn Solution>,
+    inputs: &[BatchInput],
+    answers: &Answers,
+    options: RunOptions,
+    cache: Option<&ResultCache>,
+) -> Vec<Row> {
+    let jobs = inputs
+        .iter()
+        .map(|input| Job {
+            day,
+            new,
+            input: InputSource::Path(input.path.clone()).read(day).map_err(|err| err.to_string()),
+            cache: cache.cloned(),
+        })
+        .collect();
+
+    runner::run_parallel(jobs, options)
+        .into_iter()
+        .zip(inputs)
+        .map(|(run, input)| Row {
+            expected: [1, 2].map(|part| answers.expected(&input.name, day, part)),
+            input: input.name.clone(),
+            run,
+        })
+        .collect()
+}
+
+const RED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+/// The matrix as aligned text, one row per input, followed by what went
+/// wrong with each problem row. With `color`, problem rows are also red.
+pub fn render(rows: &[Row], color: bool) -> String {
+    let answer = |a: &Option<Answer>| a.as_ref().map_or_else(|| "-".to_string(), Answer::to_string);
+    // A part that never answered has no time worth showing, and a cached
+    // one took none
+    let time = |row: &Row, part: u8, d: Duration| {
+        let cached = if part == 1 { row.run.part1_cached } else { row.run.part2_cached };
+        match row.answer(part) {
+            Some(_) if cached => "cached".to_string(),
+            Some(_) => format_duration(d),
+            None => "-".to_string(),
+        }
+    };
+    let cells: Vec<[String; 6]> = rows
+        .iter()
+        .map(|row| {
+            [
+                row.input.clone(),
+                answer(&row.run.part1),
+                time(row, 1, row.run.part1_time),
+                answer(&row.run.part2),
+                time(row, 2, row.run.part2_time),
+                row.outcome().to_string(),
+            ]
+        })
+        .collect();
+
+    let header = ["Input", "Part 1", "Part 1 time", "Part 2", "Part 2 time", "Status"];
+    let mut widths = header.map(|h| h.chars().count());
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: [&str; 6]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, width))| match i {
+                1..=4 => format!("{:>width$}", cell, width = width),
+                _ => format!("{:<width$}", cell, width = width),
+            })
+            .collect();
+        cells.join("  ").trim_end().to_string()
+    };
+
+    let mut text = line(header) + "\n";
+    for (row, cells) in rows.iter().zip(&cells) {
+        let line = line(cells.each_ref().map(String::as_str));
+        if color && row.outcome().is_problem() {
+            text += &format!("{}{}{}\n", RED, line, RESET);
+        } else {
+            text += &format!("{}\n", line);
+        }
+    }
+
+    let mut problems = Vec::new();
+    for row in rows {
+        match &row.run.status {
+            Status::Error(message) => problems.push(format!("{}: {}", row.input, message)),
+            Status::Timeout => problems.push(format!("{}: timeout", row.input)),
+            Status::Ok => {}
+        }
+        for part in [1, 2] {
+            if let (Outcome::Mismatch, Some(actual), Some(expected)) =
+                (row.part_outcome(part), row.answer(part), &row.expected[part as usize - 1])
+            {
+                problems.push(format!("{}: part {} expected {}, got {}", row.input, part, expected, actual));
+            }
+        }
+    }
+    if !problems.is_empty() {
+        text += &format!("\n{}\n", problems.join("\n"));
+    }
+    text
+}
@@ -0,0 +1,137 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+use crate::solution::Solution;
+
+/// How many times to run each phase of a day.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions { warmup: 3, runs: 10 }
+    }
+}
+
+/// Summary statistics over a set of timed runs.
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        if sorted.is_empty() {
+            sorted.push(Duration::ZERO);
+        }
+
+        let n = sorted.len();
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        // Nearest-rank percentile
+        let p95 = sorted[((n as f64 * 0.95).ceil() as usize).clamp(1, n) - 1];
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean as u64),
+            p95,
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+        }
+    }
+}
+
+/// Benchmark results for one day: parsing and each part timed separately.
+pub struct DayBench {
+    pub day: u8,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+/// Runs `f` `warmup` times untimed, then `runs` times timed.
+fn sample<T>(options: BenchOptions, mut f: impl FnMut() -> Result<T>) -> Result<Stats> {
+    for _ in 0..options.warmup {
+        black_box(f()?);
+    }
+
+    let mut samples = Vec::with_capacity(options.runs);
+    for _ in 0..options.runs.max(1) {
+        let start = Instant::now();
+        black_box(f()?);
+        samples.push(start.elapsed());
+    }
+    Ok(Stats::from_samples(&samples))
+}
+
+pub fn bench_day(
+    day: u8,
+    new: fn() -> Box<dyn Solution>,
+    input: &str,
+    options: BenchOptions,
+) -> Result<DayBench> {
+    let parse = sample(options, || (new)().parse(input))?;
+
+    let mut solution = (new)();
+    solution.parse(input)?;
+    let part1 = sample(options, || solution.part1())?;
+    let part2 = sample(options, || solution.part2())?;
+
+    Ok(DayBench {
+        day,
+        parse,
+        part1,
+        part2,
+    })
+}
+
+/// Formats a duration with a unit that keeps sub-millisecond times readable.
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1} µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2} ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2} s", nanos as f64 / 1e9)
+    }
+}
+
+/// The timings as aligned text: min, median, mean, p95 and stddev for the
+/// parse and each part.
+pub fn render(bench: &DayBench) -> String {
+    let mut text = format!(
+        "Day {:<4} {:>10} {:>10} {:>10} {:>10} {:>10}\n",
+        bench.day, "min", "median", "mean", "p95", "stddev"
+    );
+    for (name, stats) in [("parse", &bench.parse), ("part 1", &bench.part1), ("part 2", &bench.part2)] {
+        text += &format!(
+            "  {:<6} {:>10} {:>10} {:>10} {:>10} {:>10}\n",
+            name,
+            format_duration(stats.min),
+            format_duration(stats.median),
+            format_duration(stats.mean),
+            format_duration(stats.p95),
+            format_duration(stats.stddev),
+        );
+    }
+    text
+}
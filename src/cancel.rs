@@ -0,0 +1,90 @@
+//! Cooperative cancellation, so one slow day cannot stall a whole run.
+//!
+//! Whoever runs a solver gives it a [`Budget`] with [`Budget::enter`], and
+//! the long loops inside solvers call [`check`]. Once the budget's deadline
+//! passes or it is cancelled, `check` fails with [`AocError::Timeout`] and the
+//! `?` carries that out of the solver. Code that runs outside any budget is
+//! never cancelled.
+
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::error::{AocError, Result};
+
+// The clock is read on one call in this many, to keep `check` cheap in the
+// innermost loops
+const CLOCK_EVERY: u32 = 1024;
+
+/// A time limit for one solver, shared by every thread working on it.
+#[derive(Debug, Clone, Default)]
+pub struct Budget {
+    deadline: Option<Instant>,
+    cancelled: Arc<AtomicBool>,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Budget>> = const { RefCell::new(None) };
+    static CALLS: Cell<u32> = const { Cell::new(0) };
+}
+
+impl Budget {
+    /// A budget that runs out only when cancelled.
+    pub fn unlimited() -> Self {
+        Budget::default()
+    }
+
+    /// A budget that runs out `timeout` from now, or never for `None`.
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Budget {
+            deadline: timeout.and_then(|timeout| Instant::now().checked_add(timeout)),
+            cancelled: Arc::default(),
+        }
+    }
+
+    /// Makes every later [`check`] under this budget fail, on any thread.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Runs `f` with this budget in force on the current thread, restoring
+    /// whatever was in force before afterwards.
+    pub fn enter<T>(&self, f: impl FnOnce() -> T) -> T {
+        struct Restore(Option<Budget>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.clone()))));
+        f()
+    }
+}
+
+/// Fails with [`AocError::Timeout`] for `day` once the budget in force on
+/// this thread is cancelled or past its deadline.
+pub fn check(day: u8) -> Result<()> {
+    CURRENT.with(|current| {
+        let current = current.borrow();
+        let Some(budget) = current.as_ref() else {
+            return Ok(());
+        };
+        if !budget.is_cancelled() {
+            let calls = CALLS.with(|calls| {
+                calls.set(calls.get().wrapping_add(1));
+                calls.get()
+            });
+            match budget.deadline {
+                Some(deadline) if calls.is_multiple_of(CLOCK_EVERY) && Instant::now() >= deadline => budget.cancel(),
+                _ => return Ok(()),
+            }
+        }
+        Err(AocError::Timeout { day })
+    })
+}
@@ -0,0 +1,149 @@
+//! `aoc.toml`: tunable puzzle constants, one section per day, so variants of
+//! a puzzle can be explored without recompiling. Every key is optional and
+//! falls back to the puzzle's own value:
+//!
+//! ```toml
+//! [day2]
+//! min_step = 1
+//! max_step = 3
+//!
+//! [day7]
+//! part1_operators = ["+", "*"]
+//! part2_operators = ["+", "*", "|"]
+//!
+//! [day11]
+//! blinks = 75
+//!
+//! [day13]
+//! prize_offset = 10000000000000
+//! a_cost = 3
+//! b_cost = 1
+//! ```
+
+use std::fs::read_to_string;
+use std::io;
+use std::path::Path;
+use std::sync::RwLock;
+
+use toml::{Table, Value};
+
+use crate::{day11, day13, day2, day7};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// The typed settings for every configurable day.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    pub day2: day2::Config,
+    pub day7: day7::Config,
+    pub day11: day11::Config,
+    pub day13: day13::Config,
+}
+
+impl Config {
+    /// Reads `path`, or the defaults if it does not exist and `required` is
+    /// false.
+    pub fn load(path: &Path, required: bool) -> io::Result<Self> {
+        let text = match read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound && !required => return Ok(Config::default()),
+            Err(err) => return Err(io::Error::new(err.kind(), format!("{}: {}", path.display(), err))),
+        };
+        Self::parse(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), err)))
+    }
+
+    /// Parses the whole file. Unknown sections and keys are errors, so a
+    /// typo cannot silently leave a default in place.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let table: Table = text.parse().map_err(|err: toml::de::Error| err.to_string())?;
+        let mut config = Config::default();
+        for (name, value) in &table {
+            let mut section = Section::new(name, value)?;
+            match name.as_str() {
+                "day2" => config.day2 = day2::Config::from_section(&mut section)?,
+                "day7" => config.day7 = day7::Config::from_section(&mut section)?,
+                "day11" => config.day11 = day11::Config::from_section(&mut section)?,
+                "day13" => config.day13 = day13::Config::from_section(&mut section)?,
+                _ => return Err(format!("unknown section [{}]", name)),
+            }
+            section.finish()?;
+        }
+        Ok(config)
+    }
+}
+
+/// One `[dayN]` table, read a key at a time. Keys nothing asked for are
+/// reported by [`Section::finish`].
+pub struct Section<'a> {
+    name: &'a str,
+    table: &'a Table,
+    read: Vec<&'static str>,
+}
+
+impl<'a> Section<'a> {
+    fn new(name: &'a str, value: &'a Value) -> Result<Self, String> {
+        match value {
+            Value::Table(table) => Ok(Section { name, table, read: Vec::new() }),
+            _ => Err(format!("{} must be a [{}] section", name, name)),
+        }
+    }
+
+    /// An error about `key`, naming the section it is in.
+    pub fn error(&self, key: &str, message: impl std::fmt::Display) -> String {
+        format!("[{}] {}: {}", self.name, key, message)
+    }
+
+    fn get(&mut self, key: &'static str) -> Option<&'a Value> {
+        self.read.push(key);
+        self.table.get(key)
+    }
+
+    pub fn integer<T: TryFrom<i64>>(&mut self, key: &'static str, default: T) -> Result<T, String> {
+        match self.get(key) {
+            None => Ok(default),
+            Some(Value::Integer(n)) => T::try_from(*n).map_err(|_| self.error(key, format!("{} is out of range", n))),
+            Some(_) => Err(self.error(key, "expected an integer")),
+        }
+    }
+
+    /// A list of one-character strings, such as `["+", "*"]`.
+    pub fn chars(&mut self, key: &'static str, default: Vec<char>) -> Result<Vec<char>, String> {
+        let Some(value) = self.get(key) else {
+            return Ok(default);
+        };
+        let expected = || self.error(key, "expected a list of single characters");
+        let items = value.as_array().ok_or_else(expected)?;
+        items
+            .iter()
+            .map(|item| {
+                let mut chars = item.as_str().ok_or_else(expected)?.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(expected()),
+                }
+            })
+            .collect()
+    }
+
+    fn finish(self) -> Result<(), String> {
+        match self.table.keys().find(|key| !self.read.contains(&key.as_str())) {
+            Some(key) => Err(self.error(key, "unknown key")),
+            None => Ok(()),
+        }
+    }
+}
+
+static CURRENT: RwLock<Option<Config>> = RwLock::new(None);
+
+/// Makes `config` the one solvers are built with from now on.
+pub fn set(config: Config) {
+    if let Ok(mut current) = CURRENT.write() {
+        *current = Some(config);
+    }
+}
+
+/// The config set at startup, or the defaults if none was.
+pub fn current() -> Config {
+    CURRENT.read().ok().and_then(|current| current.clone()).unwrap_or_default()
+}
@@ -1,39 +1,54 @@
-use std::{collections::HashMap, fs::read_to_string};
+use std::collections::HashMap;
 
-pub fn solve_part1() -> i64 {
-   let m = read_to_string("inputs/day1.txt")
-    .unwrap();
-    calculate_total_distance(&m)
-   
-}
-pub fn solve_part2()-> i64 {
-    let m = read_to_string("inputs/day1.txt")
-    .unwrap();
-    calculate_similarity_score(&m)
+use crate::error::Result;
+use crate::parser::Parser;
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 1;
+
+#[derive(Default)]
+pub struct Day1 {
+    left_list: Vec<i64>,
+    right_list: Vec<i64>,
 }
-fn calculate_total_distance(input: &str) -> i64 {
-    // Parse input into two vectors
-    let mut left_list = Vec::new();
-    let mut right_list = Vec::new();
-    
-    // Process each line
-    for line in input.lines() {
-        if line.trim().is_empty() {
-            continue;
-        }
-        
-        let numbers: Vec<i64> = line
-            .split_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect();
-            
-        if numbers.len() == 2 {
-            left_list.push(numbers[0]);
-            right_list.push(numbers[1]);
-        }
+
+impl Solution for Day1 {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        (self.left_list, self.right_list) = parse_input(input)?;
+        Ok(())
     }
-    
+
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.left_list, &self.right_list).map(Answer::from)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        solve_part2(&self.left_list, &self.right_list).map(Answer::from)
+    }
+}
+
+pub fn solve_part1(left_list: &[i64], right_list: &[i64]) -> Result<i64> {
+    Ok(calculate_total_distance(left_list, right_list))
+}
+
+pub fn solve_part2(left_list: &[i64], right_list: &[i64]) -> Result<i64> {
+    Ok(calculate_similarity_score(left_list, right_list))
+}
+
+pub fn parse_input(input: &str) -> Result<(Vec<i64>, Vec<i64>)> {
+    let p = Parser::new(DAY, input);
+    let pairs = p.lines(input, |line| match p.integers::<i64>(line)?[..] {
+        [left, right] => Ok((left, right)),
+        _ => Err(p.error(line, "expected two location IDs")),
+    })?;
+
+    Ok(pairs.into_iter().unzip())
+}
+
+fn calculate_total_distance(left_list: &[i64], right_list: &[i64]) -> i64 {
     // Sort both lists
+    let mut left_list = left_list.to_vec();
+    let mut right_list = right_list.to_vec();
     left_list.sort_unstable();
     right_list.sort_unstable();
     
@@ -45,28 +60,7 @@ fn calculate_total_distance(input: &str) -> i64 {
 }
 
 
-fn calculate_similarity_score(input: &str) -> i64 {
-    // Parse input into two vectors
-    let mut left_list = Vec::new();
-    let mut right_list = Vec::new();
-    
-    // Process each line
-    for line in input.lines() {
-        if line.trim().is_empty() {
-            continue;
-        }
-        
-        let numbers: Vec<i64> = line
-            .split_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect();
-            
-        if numbers.len() == 2 {
-            left_list.push(numbers[0]);
-            right_list.push(numbers[1]);
-        }
-    }
-    
+fn calculate_similarity_score(left_list: &[i64], right_list: &[i64]) -> i64 {
     // Count occurrences in right list
     let right_counts: HashMap<i64, i64> = right_list
         .iter()
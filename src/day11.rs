@@ -1,54 +1,146 @@
 use std::collections::HashMap;
 
+use crate::config::{self, Section};
+use crate::error::{AocError, Result};
+use crate::event;
+use crate::parser::Parser;
+use crate::solution::{Answer, Configurable, Solution};
+
+const DAY: u8 = 11;
+
+// Each blink is a level of recursion, and by around 100 the stone count no
+// longer fits in a usize anyway
+pub const MAX_BLINKS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// How many times the stones blink, at most [`MAX_BLINKS`]. The default
+    /// is the puzzle's part 2 count, which this crate answers as part 1.
+    pub blinks: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { blinks: 75 }
+    }
+}
+
+impl Config {
+    pub fn from_section(section: &mut Section) -> std::result::Result<Self, String> {
+        let config = Config {
+            blinks: section.integer("blinks", Config::default().blinks)?,
+        };
+        if config.blinks > MAX_BLINKS {
+            return Err(section.error("blinks", format!("at most {} blinks are supported", MAX_BLINKS)));
+        }
+        Ok(config)
+    }
+}
+
+#[derive(Default)]
+pub struct Day11 {
+    stones: Vec<u64>,
+    config: Config,
+}
+
+impl Configurable for Day11 {
+    fn with_config(config: &config::Config) -> Self {
+        Day11 { stones: Vec::new(), config: config.day11.clone() }
+    }
+}
+
+impl Solution for Day11 {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        self.stones = parse_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.stones, &self.config).map(Answer::from)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        Ok(Answer::Unsolved)
+    }
+
+    fn version(&self) -> String {
+        format!("1 {:?}", self.config)
+    }
+}
+
 #[derive(Hash, Eq, PartialEq, Copy, Clone)]
 struct CacheKey {
     stone: u64,
     n: usize,
 }
 
-fn blink(stone: u64, n: usize, cache: &mut HashMap<CacheKey, usize>) -> usize {
+// Stone counts by stone and blinks left, with how often they were reused
+#[derive(Default)]
+struct Cache {
+    counts: HashMap<CacheKey, usize>,
+    hits: usize,
+}
+
+fn blink(stone: u64, n: usize, cache: &mut Cache) -> Result<usize> {
     if n == 0 {
-        return 1;
+        return Ok(1);
     }
     
     let key = CacheKey { stone, n };
-    if let Some(&result) = cache.get(&key) {
-        return result;
+    if let Some(&result) = cache.counts.get(&key) {
+        cache.hits += 1;
+        return Ok(result);
     }
     
     let result = if stone == 0 {
-        blink(1, n - 1, cache)
+        blink(1, n - 1, cache)?
     } else {
         let str_stone = stone.to_string();
-        if str_stone.len() % 2 == 0 {
+        if str_stone.len().is_multiple_of(2) {
+            // Both halves are made of the digits of a u64, so they fit in one
             let mid = str_stone.len() / 2;
-            let left = str_stone[..mid].parse::<u64>().unwrap();
-            let right = str_stone[mid..].parse::<u64>().unwrap();
-            blink(left, n - 1, cache) + blink(right, n - 1, cache)
+            let left = str_stone[..mid].parse::<u64>().unwrap_or_default();
+            let right = str_stone[mid..].parse::<u64>().unwrap_or_default();
+            blink(left, n - 1, cache)?
+                .checked_add(blink(right, n - 1, cache)?)
+                .ok_or_else(|| AocError::puzzle(DAY, "the stone count overflows"))?
         } else {
-            blink(stone * 2024, n - 1, cache)
+            let next = stone.checked_mul(2024).ok_or_else(|| {
+                AocError::puzzle(DAY, format!("stone {} overflows when multiplied by 2024", stone))
+            })?;
+            blink(next, n - 1, cache)?
         }
     };
     
-    cache.insert(key, result);
-    result
+    cache.counts.insert(key, result);
+    Ok(result)
 }
-fn parse_input(input: &str) -> Vec<u64> {
-    input
-        .trim()
-        .split_whitespace()
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect()
+pub fn parse_input(input: &str) -> Result<Vec<u64>> {
+    Parser::new(DAY, input).integers(input)
 }
 
-pub fn solve_part1() -> usize {
-   let input = parse_input(include_str!("../inputs/day11.txt"));
-    let mut cache = HashMap::new();
-    let mut total = 0;
+pub fn solve_part1(stones: &[u64], config: &Config) -> Result<usize> {
+    // A Config built in code skips the check aoc.toml gets
+    if config.blinks > MAX_BLINKS {
+        return Err(AocError::puzzle(DAY, format!("at most {} blinks are supported", MAX_BLINKS)));
+    }
+    let mut cache = Cache::default();
+    let mut total: usize = 0;
     
-    for stone in input {
-        total += blink(stone, 75, &mut cache);
+    for &stone in stones {
+        total = total
+            .checked_add(blink(stone, config.blinks, &mut cache)?)
+            .ok_or_else(|| AocError::puzzle(DAY, "the stone count overflows"))?;
     }
     
-    total
+    event!(
+        Debug,
+        "day11",
+        "blinked",
+        blinks = config.blinks,
+        stones = total,
+        cache_entries = cache.counts.len(),
+        cache_hits = cache.hits,
+    );
+    Ok(total)
 }
@@ -1,11 +1,18 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::HashSet;
 
-type Point = (i32, i32);
+use crate::error::Result;
+use crate::event;
+use crate::geom::{Direction, Point};
+use crate::grid::Grid;
+use crate::parser::Parser;
+use crate::search;
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 12;
 
-const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
 const CELL_SIZE: usize = 3;
 
-struct Region {
+pub struct Region {
     idx: char,
     positions: HashSet<Point>,
     perimeter: i32,
@@ -13,110 +20,118 @@ struct Region {
 }
 
 impl Region {
-    fn new(idx: char, start_position: Point) -> Self {
-        let mut positions = HashSet::new();
-        positions.insert(start_position);
-        Self {
-            idx,
-            positions,
-            perimeter: 0,
-            corners: 0,
-        }
+    /// The plant type growing in this region.
+    pub fn plant(&self) -> char {
+        self.idx
+    }
+
+    pub fn positions(&self) -> &HashSet<Point> {
+        &self.positions
+    }
+
+    pub fn area(&self) -> i32 {
+        self.positions.len() as i32
+    }
+
+    pub fn perimeter(&self) -> i32 {
+        self.perimeter
     }
 
-    fn price(&self) -> i32 {
+    /// A region has as many straight sides as it has corners.
+    pub fn sides(&self) -> i32 {
+        self.corners
+    }
+
+    pub fn price(&self) -> i32 {
         self.positions.len() as i32 * self.perimeter
     }
 
-    fn side_price(&self) -> i32 {
+    pub fn side_price(&self) -> i32 {
         self.positions.len() as i32 * self.corners
     }
 }
 
+#[derive(Default)]
 pub struct Field {
-    field_map: Vec<Vec<char>>,
+    field_map: Grid<char>,
     regions: Vec<Region>,
-    rows: i32,
-    cols: i32,
 }
 
 impl Field {
-   pub fn new(field_map: Vec<Vec<char>>) -> Self {
-        let rows = field_map.len() as i32;
-        let cols = field_map[0].len() as i32;
+    pub fn new(field_map: Grid<char>) -> Self {
+        let mut remaining: HashSet<Point> = field_map
+            .positions()
+            .map(Point::from_index)
+            .collect();
+        let seeds: Vec<Point> = field_map.positions().map(Point::from_index).collect();
         let mut field = Self {
             field_map,
             regions: Vec::new(),
-            rows,
-            cols,
         };
-        
-        let mut remaining: HashSet<Point> = (0..rows)
-            .flat_map(|r| (0..cols).map(move |c| (r, c)))
-            .collect();
 
-        while !remaining.is_empty() {
-            let &position = remaining.iter().next().unwrap();
-            remaining.remove(&position);
-            field.fill_region(position, &mut remaining);
+        // Seeded row by row, so the regions come out in a fixed order
+        for position in seeds {
+            if remaining.remove(&position) {
+                field.fill_region(position, &mut remaining);
+            }
         }
+        event!(Debug, "day12", "regions found", count = field.regions.len());
         
         field
     }
 
+    // The plant at a position, or None off the edge
+    fn plant_at(&self, position: Point) -> Option<char> {
+        self.field_map.get(position).copied()
+    }
+
     fn fill_region(&mut self, start: Point, remaining: &mut HashSet<Point>) {
-        let mut queue = VecDeque::new();
-        let mut visited = HashSet::new();
-        queue.push_back(start);
-        let idx = self.field_map[start.0 as usize][start.1 as usize];
-        let mut region = Region::new(idx, start);
-
-        while let Some(position) = queue.pop_front() {
-            if !visited.insert(position) {
-                continue;
-            }
+        let field = &*self;
+        let idx = field.plant_at(start).unwrap_or_default();
+        let same_plant = |&position: &Point| {
+            Direction::ORTHOGONAL
+                .into_iter()
+                .map(move |direction| position + direction)
+                .filter(move |&next| field.plant_at(next) == Some(idx))
+        };
 
-            region.corners += self.count_corners(position);
-
-            for &(dx, dy) in &DIRECTIONS {
-                let next = (position.0 + dx, position.1 + dy);
-                
-                if self.out_of_bounds(next) {
-                    region.perimeter += 1;
-                    continue;
-                }
-
-                let next_idx = self.field_map[next.0 as usize][next.1 as usize];
-                if next_idx == idx {
-                    if !visited.contains(&next) {
-                        queue.push_back(next);
-                        region.positions.insert(next);
-                    }
-                } else {
-                    region.perimeter += 1;
-                }
-            }
-        }
+        let positions: HashSet<Point> = search::flood_fill(start, &same_plant).into_iter().collect();
+        // Every side of a plot that doesn't touch the same plant is fence
+        let perimeter = positions
+            .iter()
+            .map(|&position| 4 - same_plant(&position).count() as i32)
+            .sum();
+        let corners = positions.iter().map(|&position| field.count_corners(position)).sum();
 
-        for &pos in &region.positions {
-            remaining.remove(&pos);
+        for pos in &positions {
+            remaining.remove(pos);
         }
-        self.regions.push(region);
+        event!(
+            Trace,
+            "day12",
+            "region found",
+            plant = idx,
+            start = start,
+            area = positions.len(),
+            perimeter = perimeter,
+            sides = corners,
+        );
+        self.regions.push(Region {
+            idx,
+            positions,
+            perimeter,
+            corners,
+        });
     }
 
     fn neighbourhood(&self, position: Point) -> Vec<Vec<i32>> {
         let mut neighbourhood = vec![vec![0; CELL_SIZE]; CELL_SIZE];
-        let idx = self.field_map[position.0 as usize][position.1 as usize];
-
-        for row_index in 0..CELL_SIZE {
-            for col_index in 0..CELL_SIZE {
-                let row = position.0 - 1 + row_index as i32;
-                let col = position.1 - 1 + col_index as i32;
-                
-                if !self.out_of_bounds((row, col)) {
-                    neighbourhood[row_index][col_index] = 
-                        (self.field_map[row as usize][col as usize] == idx) as i32;
-                }
+        let idx = self.plant_at(position);
+
+        for (row_index, cells) in neighbourhood.iter_mut().enumerate() {
+            for (col_index, cell) in cells.iter_mut().enumerate() {
+                let offset = Point::new(col_index as i32 - 1, row_index as i32 - 1);
+                *cell = (self.plant_at(position + offset) == idx) as i32;
             }
         }
 
@@ -146,11 +161,13 @@ impl Field {
         count
     }
 
-    fn out_of_bounds(&self, position: Point) -> bool {
-        position.0 < 0 || position.0 >= self.rows || position.1 < 0 || position.1 >= self.cols
+    /// Every region, ordered by its first plot reading row by row from the
+    /// top left.
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
     }
 
-   pub fn price(&self) -> i32 {
+    pub fn price(&self) -> i32 {
         self.regions.iter().map(|region| region.price()).sum()
     }
 
@@ -158,3 +175,35 @@ impl Field {
         self.regions.iter().map(|region| region.side_price()).sum()
     }
 }
+
+pub fn parse_input(input: &str) -> Result<Field> {
+    Parser::new(DAY, input).grid(input, Some).map(Field::new)
+}
+
+pub fn solve_part1(field: &Field) -> Result<i32> {
+    Ok(field.price())
+}
+
+pub fn solve_part2(field: &Field) -> Result<i32> {
+    Ok(field.bulk_price())
+}
+
+#[derive(Default)]
+pub struct Day12 {
+    field: Field,
+}
+
+impl Solution for Day12 {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        self.field = parse_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.field).map(Answer::from)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        solve_part2(&self.field).map(Answer::from)
+    }
+}
@@ -1,131 +1,208 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::time::Instant;
-
-#[derive(Debug, Clone)]
-pub struct Point {
-    x: i64,
-    y: i64,
-}
+use crate::config::{self, Section};
+use crate::error::{AocError, Result};
+use crate::geom::Point;
+use crate::math::{self, SolveError};
+use crate::parser::Parser;
+use crate::solution::{Answer, Configurable, Solution};
+
+const DAY: u8 = 13;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct Machine {
-    button_a: Point,
-    button_b: Point,
-    prize: Point,
+    pub button_a: Point<i64>,
+    pub button_b: Point<i64>,
+    pub prize: Point<i64>,
 }
 
-pub fn read_input(filename: &str) -> io::Result<Vec<Machine>> {
-    let path = Path::new("inputs").join(filename);
-    let file = File::open(path)?;
-    let reader = io::BufReader::new(file);
-    let mut machines = Vec::new();
-    let mut current_machine = None;
-
-    for line in reader.lines() {
-        let line = line?;
-        if line.is_empty() {
-            if let Some(machine) = current_machine.take() {
-                machines.push(machine);
-            }
-            continue;
+/// What part 2 moves the prizes by, and what each button press costs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub prize_offset: i64,
+    pub a_cost: i64,
+    pub b_cost: i64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            prize_offset: 10_000_000_000_000,
+            a_cost: 3,
+            b_cost: 1,
         }
+    }
+}
 
-        if line.starts_with("Button A") {
-            let coords: Vec<i64> = line.split(':')
-                .nth(1)
-                .unwrap()
-                .split(',')
-                .map(|v| v.split('+').nth(1).unwrap().trim().parse().unwrap())
-                .collect();
-            
-            current_machine = Some(Machine {
-                button_a: Point { x: coords[0], y: coords[1] },
-                button_b: Point { x: 0, y: 0 },
-                prize: Point { x: 0, y: 0 },
-            });
-        } else if line.starts_with("Button B") {
-            let coords: Vec<i64> = line.split(':')
-                .nth(1)
-                .unwrap()
-                .split(',')
-                .map(|v| v.split('+').nth(1).unwrap().trim().parse().unwrap())
-                .collect();
-            
-            if let Some(ref mut machine) = current_machine {
-                machine.button_b = Point { x: coords[0], y: coords[1] };
-            }
-        } else if line.starts_with("Prize") {
-            let coords: Vec<i64> = line.split(':')
-                .nth(1)
-                .unwrap()
-                .split(',')
-                .map(|v| v.split('=').nth(1).unwrap().trim().parse().unwrap())
-                .collect();
-            
-            if let Some(ref mut machine) = current_machine {
-                machine.prize = Point { x: coords[0], y: coords[1] };
+impl Config {
+    pub fn from_section(section: &mut Section) -> std::result::Result<Self, String> {
+        let defaults = Config::default();
+        let config = Config {
+            prize_offset: section.integer("prize_offset", defaults.prize_offset)?,
+            a_cost: section.integer("a_cost", defaults.a_cost)?,
+            b_cost: section.integer("b_cost", defaults.b_cost)?,
+        };
+        // Tokens are spent, never earned
+        for (key, cost) in [("a_cost", config.a_cost), ("b_cost", config.b_cost)] {
+            if cost < 0 {
+                return Err(section.error(key, "a cost cannot be negative"));
             }
         }
+        Ok(config)
+    }
+}
+
+#[derive(Default)]
+pub struct Day13 {
+    machines: Vec<Machine>,
+    config: Config,
+}
+
+impl Configurable for Day13 {
+    fn with_config(config: &config::Config) -> Self {
+        Day13 { machines: Vec::new(), config: config.day13.clone() }
+    }
+}
+
+impl Solution for Day13 {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        self.machines = parse_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.machines, &self.config).map(Answer::from)
     }
 
-    // Don't forget the last machine if file doesn't end with empty line
-    if let Some(machine) = current_machine {
-        machines.push(machine);
+    fn part2(&self) -> Result<Answer> {
+        let mut machines = self.machines.clone();
+        solve_part2(&mut machines, &self.config).map(Answer::from)
     }
 
-    Ok(machines)
+    fn version(&self) -> String {
+        format!("1 {:?}", self.config)
+    }
 }
 
-pub fn solve_part1(input: &[Machine]) -> i64 {
-    let mut cost = 0;
-    
-    for machine in input {
-        // Calculate determinant
-        let deter = machine.button_a.x * machine.button_b.y - machine.button_a.y * machine.button_b.x;
-        if deter == 0 {
-            continue;
-        }
+pub fn parse_input(input: &str) -> Result<Vec<Machine>> {
+    let p = Parser::new(DAY, input);
+    p.sections(input)
+        .into_iter()
+        .map(|section| {
+            let mut lines = section.lines();
+            let mut next = |prefix: &str, separator| {
+                let line = lines
+                    .next()
+                    .ok_or_else(|| p.error(section, format!("expected a `{}` line", prefix.trim_end())))?;
+                coords(&p, line, prefix, separator)
+            };
+            let machine = Machine {
+                button_a: next("Button A: ", '+')?,
+                button_b: next("Button B: ", '+')?,
+                prize: next("Prize: ", '=')?,
+            };
+            match lines.next() {
+                Some(extra) => Err(p.error(extra, "expected a blank line after the prize")),
+                None => Ok(machine),
+            }
+        })
+        .collect()
+}
+
+// A line such as `Button A: X+94, Y+34` or `Prize: X=8400, Y=5400`
+fn coords<'a>(p: &Parser<'a>, line: &'a str, prefix: &str, separator: char) -> Result<Point<i64>> {
+    let values = p.strip_prefix(line, prefix)?;
+    let (x, y) = p.split_once(values, ",", "two coordinates")?;
+    Ok(Point::new(p.key_value(x, "X", separator)?, p.key_value(y, "Y", separator)?))
+}
 
-        // Calculate operations using Cramer's rule
-        let op1 = machine.button_b.y * machine.prize.x - machine.button_b.x * machine.prize.y;
-        let op2 = -machine.button_a.y * machine.prize.x + machine.button_a.x * machine.prize.y;
+pub fn solve_part1(input: &[Machine], config: &Config) -> Result<i64> {
+    let mut cost: i64 = 0;
 
-        // Check if solution exists (both operations should be divisible by determinant)
-        if op1 % deter != 0 || op2 % deter != 0 {
-            continue;
+    for machine in input {
+        if let Some(tokens) = min_tokens(machine, config)? {
+            cost = cost
+                .checked_add(tokens)
+                .ok_or_else(|| AocError::puzzle(DAY, "the total cost overflows"))?;
         }
+    }
 
-        let n = op1 / deter;
-        let m = op2 / deter;
+    Ok(cost)
+}
 
-        // Only count if both values are non-negative
-        if n >= 0 && m >= 0 {
-            cost += n * 3 + m;
+// The cheapest way to win the prize, or None if it can't be reached
+fn min_tokens(machine: &Machine, config: &Config) -> Result<Option<i64>> {
+    let Machine { button_a, button_b, prize } = *machine;
+    let overflow = || AocError::puzzle(DAY, "the token count overflows");
+    let tokens = |a: i64, b: i64| {
+        let a = a.checked_mul(config.a_cost);
+        let b = b.checked_mul(config.b_cost);
+        a.zip(b).and_then(|(a, b)| a.checked_add(b)).ok_or_else(overflow)
+    };
+
+    let presses = math::solve_2x2([[button_a.x, button_b.x], [button_a.y, button_b.y]], [prize.x, prize.y]);
+    match presses {
+        Ok([a, b]) => match (a.to_integer(), b.to_integer()) {
+            // Only whole, non-negative presses count
+            (Some(a), Some(b)) if a >= 0 && b >= 0 => tokens(a, b).map(Some),
+            _ => Ok(None),
+        },
+        // Both buttons move along the same line, or one does not move at
+        // all. A button that stays put is never worth pressing, so only the
+        // other one counts. Otherwise the prize has to lie on the line too,
+        // and it is a question of how to split the presses, solved along an
+        // axis both buttons move on: x, unless either button only moves
+        // along y.
+        Err(SolveError::Singular) => {
+            let still = Point::default();
+            if button_a == still || button_b == still {
+                let presses = if button_a == still {
+                    Some(0).zip(presses_alone(button_b, prize))
+                } else {
+                    presses_alone(button_a, prize).zip(Some(0))
+                };
+                return presses.map(|(a, b)| tokens(a, b)).transpose();
+            }
+            if button_a.cross(prize) != 0 || button_b.cross(prize) != 0 {
+                return Ok(None);
+            }
+            let (a, b, c) = if button_a.x != 0 && button_b.x != 0 {
+                (button_a.x, button_b.x, prize.x)
+            } else {
+                (button_a.y, button_b.y, prize.y)
+            };
+            let Some(solutions) = math::nonnegative_solutions(a, b, c) else {
+                return Ok(None);
+            };
+            // The cost changes linearly along the solutions, so one end is cheapest
+            let ends = [solutions.nth(0), solutions.last()];
+            let costs = ends.into_iter().flatten().map(|(a, b)| tokens(a, b)).collect::<Result<Vec<_>>>()?;
+            Ok(costs.into_iter().min())
         }
+        Err(_) => Err(AocError::puzzle(DAY, "the button equations overflow")),
     }
+}
 
-    cost
+/// The number of presses of `button` alone that reach `prize`, if there is
+/// one. A button that does not move only reaches a prize at the start.
+fn presses_alone(button: Point<i64>, prize: Point<i64>) -> Option<i64> {
+    let presses = if button.x != 0 {
+        prize.x.checked_div(button.x)?
+    } else if button.y != 0 {
+        prize.y.checked_div(button.y)?
+    } else {
+        0
+    };
+    let reached = button.x.checked_mul(presses)? == prize.x && button.y.checked_mul(presses)? == prize.y;
+    (presses >= 0 && reached).then_some(presses)
 }
 
-pub fn solve_part2(input: &mut Vec<Machine>) -> i64 {
-    // Add 10_000_000_000_000 to all prize coordinates
+pub fn solve_part2(input: &mut [Machine], config: &Config) -> Result<i64> {
+    // Move every prize by the offset, 10_000_000_000_000 in the puzzle
+    let offset = config.prize_offset;
     for machine in input.iter_mut() {
-        machine.prize.x += 10_000_000_000_000;
-        machine.prize.y += 10_000_000_000_000;
+        let (x, y) = (machine.prize.x.checked_add(offset), machine.prize.y.checked_add(offset));
+        let (x, y) = x.zip(y).ok_or_else(|| AocError::puzzle(DAY, "a prize overflows when moved by the offset"))?;
+        machine.prize = Point::new(x, y);
     }
     
-    solve_part1(input)
+    solve_part1(input, config)
 }
-
-pub fn elapsed_time(name: &str, result: i64, start_time: Instant) {
-    let elapsed = start_time.elapsed();
-    let minutes = elapsed.as_secs() / 60;
-    let seconds = elapsed.as_secs() % 60;
-    let milliseconds = elapsed.subsec_millis();
-    
-    println!("{} time: {}:{}:{}", name, minutes, seconds, milliseconds);
-    println!("{} result: {}", name, result);
-}
-
@@ -1,42 +1,88 @@
-use std::fs::read_to_string;
-
-pub fn solve_part1() -> usize{
-    read_to_string("inputs/day2.txt")
-    .unwrap()
-.lines()
-.filter(|line| !line.trim().is_empty())
-.map(|line| {
-    // Parse numbers from the line
-    let levels: Vec<i32> = line
-        .split_whitespace()
-        .map(|n| n.parse().unwrap())
-        .collect();
-    
-    is_safe(&levels)
-})
-.filter(|&safe| safe)
-.count()
+use crate::config::{self, Section};
+use crate::error::Result;
+use crate::parser::Parser;
+use crate::solution::{Answer, Configurable, Solution};
+
+const DAY: u8 = 2;
+
+/// How far apart neighbouring levels of a safe report may be.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub min_step: i32,
+    pub max_step: i32,
 }
 
+impl Default for Config {
+    fn default() -> Self {
+        Config { min_step: 1, max_step: 3 }
+    }
+}
 
-pub fn solve_part2() -> usize{
-    read_to_string("inputs/day2.txt")
-    .unwrap()
-.lines()
-.filter(|line| !line.trim().is_empty())
-.map(|line| {
-    // Parse numbers from the line
-    let levels: Vec<i32> = line
-        .split_whitespace()
-        .map(|n| n.parse().unwrap())
-        .collect();
-    
-    is_safe_with_dampener(&levels)
-})
-.filter(|&safe| safe)
-.count()
+impl Config {
+    pub fn from_section(section: &mut Section) -> std::result::Result<Self, String> {
+        let defaults = Config::default();
+        let config = Config {
+            min_step: section.integer("min_step", defaults.min_step)?,
+            max_step: section.integer("max_step", defaults.max_step)?,
+        };
+        // A step of zero has no direction, so a report could not be
+        // increasing or decreasing
+        if config.min_step < 1 || config.max_step < config.min_step {
+            return Err(section.error("min_step", "the steps must satisfy 1 <= min_step <= max_step"));
+        }
+        Ok(config)
+    }
+}
+
+#[derive(Default)]
+pub struct Day2 {
+    reports: Vec<Vec<i32>>,
+    config: Config,
+}
+
+impl Configurable for Day2 {
+    fn with_config(config: &config::Config) -> Self {
+        Day2 { reports: Vec::new(), config: config.day2.clone() }
+    }
+}
+
+impl Solution for Day2 {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        self.reports = parse_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.reports, &self.config).map(Answer::from)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        solve_part2(&self.reports, &self.config).map(Answer::from)
+    }
+
+    fn version(&self) -> String {
+        format!("1 {:?}", self.config)
+    }
 }
-fn is_safe(levels: &Vec<i32>) -> bool {
+
+pub fn solve_part1(reports: &[Vec<i32>], config: &Config) -> Result<usize> {
+    Ok(reports.iter()
+        .filter(|levels| is_safe(levels, config))
+        .count())
+}
+
+pub fn solve_part2(reports: &[Vec<i32>], config: &Config) -> Result<usize> {
+    Ok(reports.iter()
+        .filter(|levels| is_safe_with_dampener(levels, config))
+        .count())
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i32>>> {
+    let p = Parser::new(DAY, input);
+    p.lines(input, |line| p.integers(line))
+}
+
+pub fn is_safe(levels: &[i32], config: &Config) -> bool {
     if levels.len() < 2 {
         return true;
     }
@@ -47,9 +93,9 @@ fn is_safe(levels: &Vec<i32>) -> bool {
     for i in 1..levels.len() {
         let diff = levels[i] - levels[i-1];
         
-        // Difference must be between 1 and 3 (inclusive) for increasing
-        // or between -3 and -1 (inclusive) for decreasing
-        if diff == 0 || diff.abs() > 3 {
+        // Difference must be between min_step and max_step (inclusive) for
+        // increasing, or between -max_step and -min_step for decreasing
+        if !(config.min_step..=config.max_step).contains(&diff.abs()) {
             return false;
         }
         
@@ -68,21 +114,21 @@ fn is_safe(levels: &Vec<i32>) -> bool {
     true
 }
 
-fn is_safe_with_dampener(levels: &Vec<i32>) -> bool {
+pub fn is_safe_with_dampener(levels: &[i32], config: &Config) -> bool {
     // If it's already safe, no need to try removing elements
-    if is_safe(levels) {
+    if is_safe(levels, config) {
         return true;
     }
     
     // Try removing each element one at a time
     for i in 0..levels.len() {
-        let mut modified_levels = levels.clone();
+        let mut modified_levels = levels.to_vec();
         modified_levels.remove(i);
         
-        if is_safe(&modified_levels) {
+        if is_safe(&modified_levels, config) {
             return true;
         }
     }
     
     false
-}
\ No newline at end of file
+}
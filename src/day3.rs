@@ -1,72 +1,94 @@
-use regex::Regex;
+use crate::error::Result;
+use crate::parser::{Cursor, Parser};
+use crate::solution::{Answer, Solution};
 
-pub fn solve_part1() -> i32 {
-    let input = include_str!("../inputs/day3.txt");
-    // Create a regex pattern for valid mul instructions
-    // Matches mul(X,Y) where X and Y are 1-3 digits
-    let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-    
-    // Find all valid matches and sum their products
-    re.captures_iter(input)
-        .map(|cap| {
-            // Parse the two numbers from each capture
-            let x: i32 = cap[1].parse().unwrap();
-            let y: i32 = cap[2].parse().unwrap();
-            x * y
+const DAY: u8 = 3;
+
+#[derive(Default)]
+pub struct Day3 {
+    instructions: Vec<Instruction>,
+}
+
+impl Solution for Day3 {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        self.instructions = parse_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.instructions).map(Answer::from)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        solve_part2(&self.instructions).map(Answer::from)
+    }
+}
+
+pub fn solve_part1(instructions: &[Instruction]) -> Result<i64> {
+    // Sum the products of every mul instruction, ignoring do()/don't()
+    Ok(instructions.iter()
+        .map(|instruction| match instruction {
+            Instruction::Multiply(x, y) => i64::from(*x) * i64::from(*y),
+            _ => 0,
         })
-        .sum()
+        .sum())
 }
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     Multiply(i32, i32),
     Do,
     Dont,
 }
 
-fn parse_instructions(input: &str) -> Vec<Instruction> {
-   
-    
-    // Create regex patterns for all instruction types
-    let mul_re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-    let do_re = Regex::new(r"do\(\)").unwrap();
-    let dont_re = Regex::new(r"don't\(\)").unwrap();
-    
-    // Find all matches and their positions
-    let mut matches = vec![];
-    
-    // Find multiplication instructions
-    for cap in mul_re.captures_iter(input) {
-        let pos = cap.get(0).unwrap().start();
-        let x: i32 = cap[1].parse().unwrap();
-        let y: i32 = cap[2].parse().unwrap();
-        matches.push((pos, Instruction::Multiply(x, y)));
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>> {
+    let p = Parser::new(DAY, input);
+    let mut cursor = Cursor::new(input);
+    let mut instructions = Vec::new();
+
+    // Try an instruction at every position; anything else is corrupted memory
+    while !cursor.is_empty() {
+        let mut attempt = cursor;
+        match instruction(&p, &mut attempt)? {
+            Some(instruction) => {
+                instructions.push(instruction);
+                cursor = attempt;
+            }
+            None => cursor.skip(),
+        }
     }
-    
-    // Find do() instructions
-    for mat in do_re.find_iter(input) {
-        matches.push((mat.start(), Instruction::Do));
+
+    Ok(instructions)
+}
+
+// `mul(X,Y)` with 1-3 digit operands, `do()` or `don't()`
+fn instruction<'a>(p: &Parser<'a>, cursor: &mut Cursor<'a>) -> Result<Option<Instruction>> {
+    if cursor.eat("do()") {
+        return Ok(Some(Instruction::Do));
     }
-    
-    // Find don't() instructions
-    for mat in dont_re.find_iter(input) {
-        matches.push((mat.start(), Instruction::Dont));
+    if cursor.eat("don't()") {
+        return Ok(Some(Instruction::Dont));
     }
-    
-    // Sort by position to maintain order
-    matches.sort_by_key(|&(pos, _)| pos);
-    
-    // Extract just the instructions in order
-    matches.into_iter().map(|(_, instr)| instr).collect()
+    if !cursor.eat("mul(") {
+        return Ok(None);
+    }
+    let Some(x) = cursor.digits(3) else { return Ok(None) };
+    if !cursor.eat(",") {
+        return Ok(None);
+    }
+    let Some(y) = cursor.digits(3) else { return Ok(None) };
+    if !cursor.eat(")") {
+        return Ok(None);
+    }
+    Ok(Some(Instruction::Multiply(p.integer(x)?, p.integer(y)?)))
 }
 
-pub fn solve_part2() -> i64 {
-    let instructions = parse_instructions(include_str!("../inputs/day3.txt"));
+pub fn solve_part2(instructions: &[Instruction]) -> Result<i64> {
     let mut enabled = true;
     let mut sum = 0;
     
     for instruction in instructions {
-        match instruction {
+        match *instruction {
             Instruction::Multiply(x, y) if enabled => {
                 sum += i64::from(x) * i64::from(y);
             }
@@ -76,5 +98,5 @@ pub fn solve_part2() -> i64 {
         }
     }
     
-    sum
+    Ok(sum)
 }
\ No newline at end of file
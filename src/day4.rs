@@ -1,72 +1,50 @@
-use std::collections::HashSet;
+use crate::error::Result;
+use crate::geom::{Direction, Point};
+use crate::grid::Grid;
+use crate::parser::Parser;
+use crate::solution::{Answer, Solution};
 
-const  Input :&str = include_str!("../inputs/day4.txt");
+const DAY: u8 = 4;
 
+#[derive(Default)]
+pub struct Day4 {
+    grid: Grid<char>,
+}
 
-fn count_xmas(input: &str) -> usize {
-    let grid: Vec<Vec<char>> = input.lines()
-        .map(|line| line.chars().collect())
-        .collect();
-    let rows = grid.len();
-    let cols = grid[0].len();
-    let mut count = 0;
+impl Solution for Day4 {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        self.grid = parse_input(input)?;
+        Ok(())
+    }
 
-    // Check all possible directions
-    let directions = [
-        (0, 1),   // right
-        (1, 0),   // down
-        (1, 1),   // diagonal down-right
-        (-1, 1),  // diagonal up-right
-        (0, -1),  // left
-        (-1, 0),  // up
-        (-1, -1), // diagonal up-left
-        (1, -1),  // diagonal down-left
-    ];
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.grid).map(Answer::from)
+    }
 
-    for i in 0..rows {
-        for j in 0..cols {
-            for &(di, dj) in &directions {
-                if check_xmas(&grid, i, j, di, dj) {
-                    count += 1;
-                }
-            }
-        }
+    fn part2(&self) -> Result<Answer> {
+        solve_part2(&self.grid).map(Answer::from)
     }
+}
 
-    count
+pub fn solve_part1(grid: &Grid<char>) -> Result<usize> {
+    Ok(count_xmas(grid))
 }
 
-fn check_xmas(grid: &[Vec<char>], i: usize, j: usize, di: i32, dj: i32) -> bool {
-    let target = ['X', 'M', 'A', 'S'];
-    let rows = grid.len() as i32;
-    let cols = grid[0].len() as i32;
-    
-    for k in 0..4 {
-        let ni = i as i32 + di * k;
-        let nj = j as i32 + dj * k;
-        
-        if ni < 0 || ni >= rows || nj < 0 || nj >= cols {
-            return false;
-        }
-        
-        if grid[ni as usize][nj as usize] != target[k as usize] {
-            return false;
-        }
-    }
-    true
+pub fn solve_part2(grid: &Grid<char>) -> Result<usize> {
+    Ok(count_x_mas(grid))
+}
+
+pub fn parse_input(input: &str) -> Result<Grid<char>> {
+    Parser::new(DAY, input).grid(input, Some)
 }
 
-fn count_x_mas(input: &str) -> usize {
-    let grid: Vec<Vec<char>> = input.lines()
-        .map(|line| line.chars().collect())
-        .collect();
-    let rows = grid.len();
-    let cols = grid[0].len();
+fn count_xmas(grid: &Grid<char>) -> usize {
     let mut count = 0;
 
-    for i in 1..rows-1 {
-        for j in 1..cols-1 {
-            if check_x_mas(&grid, i, j) {
+    // Check all possible directions
+    for position in grid.positions() {
+        for direction in Direction::ALL {
+            if check_xmas(grid, Point::from_index(position), direction) {
                 count += 1;
             }
         }
@@ -75,32 +53,35 @@ fn count_x_mas(input: &str) -> usize {
     count
 }
 
-fn check_x_mas(grid: &[Vec<char>], i: usize, j: usize) -> bool {
-    // Check for MAS in both directions forming an X
-    let arms = [
-        [(i-1, j-1), (i, j), (i+1, j+1)],  // top-left to bottom-right
-        [(i-1, j+1), (i, j), (i+1, j-1)]   // top-right to bottom-left
-    ];
+fn check_xmas(grid: &Grid<char>, start: Point, direction: Direction) -> bool {
+    // The ray stops at the edge, so a word running off the grid is too short
+    grid.ray(start, direction.offset()).take(4).eq(&['X', 'M', 'A', 'S'])
+}
 
-    let mut found_mas = 0;
-    let target = ['M', 'A', 'S'];
+fn count_x_mas(grid: &Grid<char>) -> usize {
+    let mut count = 0;
 
-    for arm in &arms {
-        // Check forwards
-        if arm.iter().enumerate().all(|(k, &(r, c))| grid[r][c] == target[k]) {
-            found_mas += 1;
-        }
-        // Check backwards
-        if arm.iter().enumerate().all(|(k, &(r, c))| grid[r][c] == target[2-k]) {
-            found_mas += 1;
+    for position in grid.find_all(&'A') {
+        if check_x_mas(grid, Point::from_index(position)) {
+            count += 1;
         }
     }
 
-    found_mas == 2
-}
-pub fn solve_part1() -> usize {
-    count_xmas(Input)
+    count
 }
-pub fn solve_part2() -> usize {
-    count_x_mas(Input)
+
+fn check_x_mas(grid: &Grid<char>, centre: Point) -> bool {
+    // Check for MAS in both directions forming an X
+    let arms = [
+        Direction::NorthWest, // top-left to bottom-right
+        Direction::NorthEast, // top-right to bottom-left
+    ];
+
+    arms.iter().all(|&arm| {
+        // Either way round: M then S, or S then M
+        matches!(
+            (grid.get(centre + arm), grid.get(centre + arm.reverse())),
+            (Some('M'), Some('S')) | (Some('S'), Some('M'))
+        )
+    })
 }
@@ -1,33 +1,50 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
 
-fn parse_input(input: &str) -> (Vec<(u32, u32)>, Vec<Vec<u32>>) {
-    let parts: Vec<_> = input.trim().split("\n\n").collect();
-    
-    // Parse rules
-    let rules: Vec<(u32, u32)> = parts[0]
-        .lines()
-        .map(|line| {
-            let nums: Vec<u32> = line.split('|')
-                .map(|n| n.parse().unwrap())
-                .collect();
-            (nums[0], nums[1])
-        })
-        .collect();
-    
-    // Parse updates
-    let updates: Vec<Vec<u32>> = parts[1]
-        .lines()
-        .map(|line| {
-            line.split(',')
-                .map(|n| n.parse().unwrap())
-                .collect()
-        })
-        .collect();
-    
-    (rules, updates)
+use crate::error::{AocError, Result};
+use crate::parser::Parser;
+use crate::search;
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 5;
+
+#[derive(Default)]
+pub struct Day5 {
+    rules: Vec<(u32, u32)>,
+    updates: Vec<Vec<u32>>,
+}
+
+impl Solution for Day5 {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        (self.rules, self.updates) = parse_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.rules, &self.updates).map(Answer::from)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        solve_part2(&self.rules, &self.updates).map(Answer::from)
+    }
 }
 
-fn is_valid_order(update: &[u32], rules: &[(u32, u32)]) -> bool {
+pub type Rules = Vec<(u32, u32)>;
+pub type Updates = Vec<Vec<u32>>;
+
+pub fn parse_input(input: &str) -> Result<(Rules, Updates)> {
+    let p = Parser::new(DAY, input);
+    let [rules, updates] = p.sections_exact(input, "rules and updates separated by a blank line")?;
+
+    let rules = p.lines(rules, |line| {
+        let (before, after) = p.split_once(line, "|", "a rule like `47|53`")?;
+        Ok((p.integer(before)?, p.integer(after)?))
+    })?;
+    let updates = p.lines(updates, |line| p.list(line, ",", |n| p.integer(n)))?;
+
+    Ok((rules, updates))
+}
+
+pub fn is_valid_order(update: &[u32], rules: &[(u32, u32)]) -> bool {
     let positions: HashMap<u32, usize> = update
         .iter()
         .enumerate()
@@ -46,74 +63,29 @@ fn is_valid_order(update: &[u32], rules: &[(u32, u32)]) -> bool {
     
     true
 }
-fn topological_sort(pages: &[u32], rules: &[(u32, u32)]) -> Vec<u32> {
-    let mut graph: HashMap<u32, Vec<u32>> = HashMap::new();
-    let mut in_degree: HashMap<u32, usize> = HashMap::new();
-    let pages_set: HashSet<_> = pages.iter().copied().collect();
-    
-    // Initialize in-degree counts to 0
-    for &page in pages {
-        in_degree.insert(page, 0);
-    }
-    
-    // Build graph and count incoming edges
-    for &(before, after) in rules {
-        if pages_set.contains(&before) && pages_set.contains(&after) {
-            graph.entry(before).or_default().push(after);
-            *in_degree.entry(after).or_default() += 1;
-        }
-    }
-    
-    // Start with nodes that have no dependencies
-    let mut queue: VecDeque<u32> = in_degree
-        .iter()
-        .filter(|&(_, &count)| count == 0)
-        .map(|(&page, _)| page)
-        .collect();
-    
-    let mut result = Vec::new();
-    
-    // Process queue
-    while let Some(page) = queue.pop_front() {
-        result.push(page);
-        
-        if let Some(neighbors) = graph.get(&page) {
-            for &next in neighbors {
-                *in_degree.get_mut(&next).unwrap() -= 1;
-                if in_degree[&next] == 0 {
-                    queue.push_back(next);
-                }
-            }
-        }
-    }
-    
-    // If we couldn't order all pages (cycle in graph), return original order
-    if result.len() != pages.len() {
-        pages.to_vec()
-    } else {
-        result
-    }
+/// The pages of an update reordered to follow every rule that applies to them.
+pub fn topological_sort(pages: &[u32], rules: &[(u32, u32)]) -> Result<Vec<u32>> {
+    search::topological_sort(pages.iter().copied(), rules.iter().copied()).map_err(|cycle| {
+        AocError::puzzle(DAY, format!("the rules for pages {:?} form a cycle", cycle.nodes))
+    })
 }
-pub fn solve_part1() -> u32 {
-    let (rules, updates) = parse_input(include_str!("../inputs/day5.txt"));
-    
-    updates.iter()
-        .filter(|update| is_valid_order(update, &rules))
+
+pub fn solve_part1(rules: &[(u32, u32)], updates: &[Vec<u32>]) -> Result<u32> {
+    Ok(updates.iter()
+        .filter(|update| is_valid_order(update, rules))
         .map(|update| {
             // Get middle page number
             update[update.len() / 2]
         })
-        .sum()
+        .sum())
 }
 
-pub fn solve_part2() -> u32 {
-    let (rules, updates) = parse_input(include_str!("../inputs/day5.txt"));
-    
+pub fn solve_part2(rules: &[(u32, u32)], updates: &[Vec<u32>]) -> Result<u32> {
     updates.iter()
-        .filter(|update| !is_valid_order(update, &rules))
+        .filter(|update| !is_valid_order(update, rules))
         .map(|update| {
-            let sorted = topological_sort(update, &rules);
-            sorted[sorted.len() / 2]
+            let sorted = topological_sort(update, rules)?;
+            Ok(sorted[sorted.len() / 2])
         })
         .sum()
-}
\ No newline at end of file
+}
@@ -1,35 +1,38 @@
-use std::collections::{HashSet, VecDeque};
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+use std::collections::HashSet;
+
+use crate::cancel;
+use crate::error::{AocError, Result};
+use crate::event;
+use crate::geom::{Direction, Point};
+use crate::grid::Grid;
+use crate::parser::Parser;
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 6;
+
+#[derive(Default)]
+pub struct Day6 {
+    map: Grid<char>,
 }
 
-impl Direction {
-    fn turn_right(&self) -> Self {
-        match self {
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-        }
+impl Solution for Day6 {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        self.map = parse_input(input)?;
+        Ok(())
     }
 
-    fn move_position(&self, (x, y): (i32, i32)) -> (i32, i32) {
-        match self {
-            Direction::Up => (x, y - 1),
-            Direction::Right => (x + 1, y),
-            Direction::Down => (x, y + 1),
-            Direction::Left => (x - 1, y),
-        }
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.map).map(Answer::from)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        solve_part2(&self.map).map(Answer::from)
     }
 }
-fn solve_guard_patrol(map: &Vec<Vec<char>>) -> usize {
+
+fn solve_guard_patrol(map: &Grid<char>) -> Result<usize> {
     // Find starting position and direction
-    let (mut current_pos, mut current_dir) = find_starting_info(map);
+    let (mut current_pos, mut current_dir) = find_starting_info(map)?;
     
     // Track visited positions
     let mut visited_positions = HashSet::new();
@@ -37,16 +40,24 @@ fn solve_guard_patrol(map: &Vec<Vec<char>>) -> usize {
 
     // Safety counter to prevent infinite loop
     let mut iterations = 0;
-    let max_iterations = map.len() * map[0].len() * 2; // Generous upper bound
+    let max_iterations = map.width() * map.height() * 2; // Generous upper bound
 
     while iterations < max_iterations {
         // Check next position
-        let next_pos = current_dir.move_position(current_pos);
+        let next_pos = current_pos + current_dir;
         
         // Check if out of bounds
         if is_out_of_bounds(map, next_pos) {
-            println!("Left map at: {:?}", current_pos);
-            break;
+            event!(
+                Debug,
+                "day6",
+                "guard left the map",
+                position = current_pos,
+                direction = current_dir,
+                visited = visited_positions.len(),
+                iterations = iterations,
+            );
+            return Ok(visited_positions.len());
         }
 
         // Check if blocked
@@ -62,61 +73,68 @@ fn solve_guard_patrol(map: &Vec<Vec<char>>) -> usize {
         iterations += 1;
     }
 
-    println!("Visited positions: {}", visited_positions.len());
-    println!("Last position: {:?}", current_pos);
-    println!("Last direction: {:?}", current_dir);
-
-    visited_positions.len()
+    event!(
+        Warn,
+        "day6",
+        "guard still on the map after the step limit",
+        position = current_pos,
+        direction = current_dir,
+        visited = visited_positions.len(),
+    );
+    Ok(visited_positions.len())
 }
 
-fn solve_guard_patrol_loop_positions(map: &Vec<Vec<char>>) -> usize {
-    let (start_pos, start_dir) = find_starting_info(map);
+fn solve_guard_patrol_loop_positions(map: &Grid<char>) -> Result<usize> {
+    let (start_pos, _) = find_starting_info(map)?;
     let mut loop_positions = 0;
 
-    for y in 0..map.len() {
-        for x in 0..map[0].len() {
-            // Skip start position and already blocked positions
-            if (x as i32, y as i32) == start_pos || map[y][x] == '#' {
-                continue;
-            }
-
-            // Try placing an obstruction
-            let mut modified_map = map.clone();
-            modified_map[y][x] = '#';
-
-            // Check if this new obstruction creates a loop
-            if creates_loop(&modified_map) {
-                loop_positions += 1;
-            }
+    for (x, y) in map.positions() {
+        cancel::check(DAY)?;
+
+        // Skip start position and already blocked positions
+        if Point::from_index((x, y)) == start_pos || map[(x, y)] == '#' {
+            continue;
+        }
+
+        // Try placing an obstruction
+        let mut modified_map = map.clone();
+        modified_map[(x, y)] = '#';
+
+        // Check if this new obstruction creates a loop
+        if creates_loop(&modified_map)? {
+            event!(Trace, "day6", "obstruction makes a loop", x = x, y = y);
+            loop_positions += 1;
         }
     }
 
-    loop_positions
+    event!(Debug, "day6", "loop positions found", count = loop_positions);
+    Ok(loop_positions)
 }
 
-fn creates_loop(map: &Vec<Vec<char>>) -> bool {
-    let (start_pos, start_dir) = find_starting_info(map);
+fn creates_loop(map: &Grid<char>) -> Result<bool> {
+    let (start_pos, start_dir) = find_starting_info(map)?;
     let mut visited_states = HashSet::new();
-    let max_steps = map.len() * map[0].len() * 10; // Prevent truly infinite loops
+    let max_steps = map.width() * map.height() * 10; // Prevent truly infinite loops
 
     let mut current_pos = start_pos;
     let mut current_dir = start_dir;
 
     for _ in 0..max_steps {
+        cancel::check(DAY)?;
         let state = (current_pos, current_dir);
         
         // If we've seen this exact state before, we're in a loop
         if visited_states.contains(&state) {
-            return true;
+            return Ok(true);
         }
         visited_states.insert(state);
 
         // Check next position
-        let next_pos = current_dir.move_position(current_pos);
+        let next_pos = current_pos + current_dir;
         
         // Check if out of bounds
         if is_out_of_bounds(map, next_pos) {
-            return false;
+            return Ok(false);
         }
 
         // Check if blocked
@@ -129,39 +147,39 @@ fn creates_loop(map: &Vec<Vec<char>>) -> bool {
         }
     }
 
-    false
+    Ok(false)
 }
 
-fn find_starting_info(map: &Vec<Vec<char>>) -> ((i32, i32), Direction) {
-    for (y, row) in map.iter().enumerate() {
-        for (x, &ch) in row.iter().enumerate() {
-            match ch {
-                '^' => return ((x as i32, y as i32), Direction::Up),
-                '>' => return ((x as i32, y as i32), Direction::Right),
-                'v' => return ((x as i32, y as i32), Direction::Down),
-                '<' => return ((x as i32, y as i32), Direction::Left),
-                _ => continue,
-            }
-        }
+fn find_starting_info(map: &Grid<char>) -> Result<(Point, Direction)> {
+    for (position, &ch) in map.iter() {
+        let direction = match ch {
+            '^' => Direction::North,
+            '>' => Direction::East,
+            'v' => Direction::South,
+            '<' => Direction::West,
+            _ => continue,
+        };
+        return Ok((Point::from_index(position), direction));
     }
-    panic!("No starting position found")
+    Err(AocError::puzzle(DAY, "no starting position found"))
+}
+
+fn is_out_of_bounds(map: &Grid<char>, position: Point) -> bool {
+    !map.contains(position)
 }
 
-fn is_out_of_bounds(map: &Vec<Vec<char>>, (x, y): (i32, i32)) -> bool {
-    x < 0 || y < 0 || x >= map[0].len() as i32 || y >= map.len() as i32
+fn is_blocked(map: &Grid<char>, position: Point) -> bool {
+    map.get(position) == Some(&'#')
 }
 
-fn is_blocked(map: &Vec<Vec<char>>, (x, y): (i32, i32)) -> bool {
-    map[y as usize][x as usize] == '#'
+pub fn parse_input(input: &str) -> Result<Grid<char>> {
+    Parser::new(DAY, input).grid(input, Some)
 }
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+pub fn solve_part1(map: &Grid<char>) -> Result<usize> {
+    solve_guard_patrol(map)
 }
 
-pub fn solve_part1() -> usize{
-     solve_guard_patrol(&parse_input(include_str!("../inputs/day6.txt")))
+pub fn solve_part2(map: &Grid<char>) -> Result<usize> {
+    solve_guard_patrol_loop_positions(map)
 }
-pub fn solve_part2() -> usize{
-    solve_guard_patrol_loop_positions(&parse_input(include_str!("../inputs/day6.txt")))
-}
\ No newline at end of file
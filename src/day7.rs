@@ -1,108 +1,195 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use crate::cancel;
+use crate::config::{self, Section};
+use crate::error::{AocError, Result};
+use crate::event;
+use crate::parser::Parser;
+use crate::solution::{Answer, Configurable, Solution};
+
+const DAY: u8 = 7;
 
 #[derive(Debug)]
-struct Equation {
-    test_value: i64,
-    numbers: Vec<i64>,
+pub struct Equation {
+    pub test_value: i64,
+    pub numbers: Vec<i64>,
+}
+
+// Every operator evaluate_expression knows
+const OPERATORS: [char; 3] = ['+', '*', '|'];
+
+/// The operators each part may put between the numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub part1_operators: Vec<char>,
+    pub part2_operators: Vec<char>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        // Part 1 only has addition and multiplication; part 2 adds
+        // concatenation
+        Config {
+            part1_operators: vec!['+', '*'],
+            part2_operators: vec!['+', '*', '|'],
+        }
+    }
+}
+
+impl Config {
+    pub fn from_section(section: &mut Section) -> std::result::Result<Self, String> {
+        let defaults = Config::default();
+        let config = Config {
+            part1_operators: section.chars("part1_operators", defaults.part1_operators)?,
+            part2_operators: section.chars("part2_operators", defaults.part2_operators)?,
+        };
+        for (key, ops) in [("part1_operators", &config.part1_operators), ("part2_operators", &config.part2_operators)] {
+            if ops.is_empty() {
+                return Err(section.error(key, "needs at least one operator"));
+            }
+            if let Some(op) = ops.iter().find(|op| !OPERATORS.contains(op)) {
+                return Err(section.error(key, format!("unknown operator `{}`, expected one of + * |", op)));
+            }
+        }
+        Ok(config)
+    }
+}
+
+#[derive(Default)]
+pub struct Day7 {
+    equations: Vec<Equation>,
+    config: Config,
+}
+
+impl Configurable for Day7 {
+    fn with_config(config: &config::Config) -> Self {
+        Day7 { equations: Vec::new(), config: config.day7.clone() }
+    }
 }
 
-fn concatenate_numbers(a: i64, b: i64) -> i64 {
+impl Solution for Day7 {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        self.equations = parse_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.equations, &self.config).map(Answer::from)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        solve_part2(&self.equations, &self.config).map(Answer::from)
+    }
+
+    fn version(&self) -> String {
+        format!("1 {:?}", self.config)
+    }
+}
+
+// None if the concatenation does not fit in an i64
+fn concatenate_numbers(a: i64, b: i64) -> Option<i64> {
     let b_digits = b.to_string();
     let combined = format!("{}{}", a, b_digits);
-    combined.parse().unwrap()
+    combined.parse().ok()
 }
 
-fn evaluate_expression(numbers: &[i64], operators: &[char]) -> i64 {
+// Evaluates left to right; None once an intermediate result overflows,
+// since it can then never equal a test value
+fn evaluate_expression(numbers: &[i64], operators: &[char]) -> Result<Option<i64>> {
     let mut result = numbers[0];
     for (i, &op) in operators.iter().enumerate() {
-        match op {
-            '+' => result += numbers[i + 1],
-            '*' => result *= numbers[i + 1],
-            '|' => result = concatenate_numbers(result, numbers[i + 1]),
-            _ => panic!("Invalid operator"),
+        let value = match op {
+            '+' => result.checked_add(numbers[i + 1]),
+            '*' => result.checked_mul(numbers[i + 1]),
+            '|' => concatenate_numbers(result, numbers[i + 1]),
+            _ => return Err(AocError::puzzle(DAY, format!("invalid operator `{}`", op))),
+        };
+        match value {
+            Some(value) => result = value,
+            None => return Ok(None),
         }
     }
-    result
+    Ok(Some(result))
 }
 
-fn generate_operator_combinations(length: usize) -> Vec<Vec<char>> {
-    let ops = vec!['+', '*', '|'];
+fn generate_operator_combinations(length: usize, ops: &[char]) -> Result<Vec<Vec<char>>> {
     let mut combinations = Vec::new();
     
     fn backtrack(
         current: &mut Vec<char>, 
-        length: usize, 
         max_length: usize,
         ops: &[char], 
         combinations: &mut Vec<Vec<char>>
-    ) {
+    ) -> Result<()> {
+        // There are ops.len()^max_length combinations, so long equations
+        // can take a while
+        cancel::check(DAY)?;
         if current.len() == max_length {
             combinations.push(current.clone());
-            return;
+            return Ok(());
         }
         
         if current.len() < max_length {
             for &op in ops {
                 current.push(op);
-                backtrack(current, length, max_length, ops, combinations);
+                backtrack(current, max_length, ops, combinations)?;
                 current.pop();
             }
         }
+        Ok(())
     }
     
     let mut current = Vec::new();
-    backtrack(&mut current, length, length - 1, &ops, &mut combinations);
-    combinations
+    backtrack(&mut current, length - 1, ops, &mut combinations)?;
+    Ok(combinations)
 }
 
 
-fn solve_equation(equation: &Equation) -> bool {
-    let operator_combinations = generate_operator_combinations(equation.numbers.len());
+fn solve_equation(equation: &Equation, ops: &[char]) -> Result<bool> {
+    let operator_combinations = generate_operator_combinations(equation.numbers.len(), ops)?;
     
     for ops in operator_combinations {
-        let result = evaluate_expression(&equation.numbers, &ops);
-        if result == equation.test_value {
-            return true;
+        cancel::check(DAY)?;
+        let result = evaluate_expression(&equation.numbers, &ops)?;
+        if result == Some(equation.test_value) {
+            return Ok(true);
         }
     }
     
-    false
+    Ok(false)
 }
 
-fn parse_input(filename: &str) -> Vec<Equation> {
-    let file = File::open(filename).expect("Unable to open file");
-    let reader = BufReader::new(file);
-    
-    reader.lines()
-        .map(|line| {
-            let line = line.expect("Could not read line");
-            let parts: Vec<&str> = line.split(": ").collect();
-            let test_value: i64 = parts[0].parse().expect("Invalid test value");
-            let numbers: Vec<i64> = parts[1].split_whitespace()
-                .map(|num| num.parse().expect("Invalid number"))
-                .collect();
-            
-            Equation { test_value, numbers }
-        })
-        .collect()
+pub fn parse_input(input: &str) -> Result<Vec<Equation>> {
+    let p = Parser::new(DAY, input);
+    p.lines(input, |line| {
+        let (test_value, numbers) = p.split_once(line, ":", "`<test value>: <numbers>`")?;
+        let test_value = p.integer(test_value)?;
+        let numbers = p.integers(numbers)?;
+        if numbers.is_empty() {
+            return Err(p.error(line, "equation has no numbers"));
+        }
+
+        Ok(Equation { test_value, numbers })
+    })
 }
-pub fn solve_part1()->i64{
-    let equations = parse_input("inputs/day7.txt");
-    
-     equations.iter()
-        .filter(|eq| solve_equation(eq))
-        .map(|eq| eq.test_value)
-        .sum()
 
-        //solve_equation has been updated to fit part2 to reduce redundant code 
+fn sum_solvable(equations: &[Equation], ops: &[char]) -> Result<i64> {
+    let mut total: i64 = 0;
+    let mut solvable = 0;
+    for eq in equations {
+        if solve_equation(eq, ops)? {
+            total = total
+                .checked_add(eq.test_value)
+                .ok_or_else(|| AocError::puzzle(DAY, "sum overflows i64"))?;
+            solvable += 1;
+        }
+    }
+    event!(Debug, "day7", "equations solved", operators = ops, solvable = solvable, of = equations.len());
+    Ok(total)
 }
 
-pub fn solve_part2()->i64{
-    let equations = parse_input("inputs/day7.txt");
-    
-     equations.iter()
-        .filter(|eq| solve_equation(eq))
-        .map(|eq| eq.test_value)
-        .sum()
-}
\ No newline at end of file
+pub fn solve_part1(equations: &[Equation], config: &Config) -> Result<i64> {
+    sum_solvable(equations, &config.part1_operators)
+}
+
+pub fn solve_part2(equations: &[Equation], config: &Config) -> Result<i64> {
+    sum_solvable(equations, &config.part2_operators)
+}
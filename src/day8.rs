@@ -1,28 +1,76 @@
 use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
-struct Point {
-    x: i32,
-    y: i32,
+use crate::error::{AocError, Result};
+use crate::geom::{Point, Vec2};
+use crate::grid::Grid;
+use crate::math;
+use crate::parser::Parser;
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 8;
+
+#[derive(Default)]
+pub struct AntennaMap {
+    frequencies: HashMap<char, Vec<Point>>,
+    grid: Grid<char>,
 }
 
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Point { x, y }
+impl AntennaMap {
+    /// Antenna positions grouped by frequency.
+    pub fn frequencies(&self) -> &HashMap<char, Vec<Point>> {
+        &self.frequencies
+    }
+
+    pub fn width(&self) -> i32 {
+        self.grid.width() as i32
+    }
+
+    pub fn height(&self) -> i32 {
+        self.grid.height() as i32
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        self.grid.contains(point)
     }
 }
 
-fn parse_antenna_map(input: &str) -> HashMap<char, Vec<Point>> {
+#[derive(Default)]
+pub struct Day8 {
+    map: AntennaMap,
+}
+
+impl Solution for Day8 {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        self.map = parse_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.map).map(Answer::from)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        solve_part2(&self.map).map(Answer::from)
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<AntennaMap> {
+    let grid = Parser::new(DAY, input).grid(input, Some)?;
+    Ok(AntennaMap {
+        frequencies: parse_antenna_map(&grid),
+        grid,
+    })
+}
+
+fn parse_antenna_map(grid: &Grid<char>) -> HashMap<char, Vec<Point>> {
     let mut frequencies: HashMap<char, Vec<Point>> = HashMap::new();
     
-    for (y, line) in input.lines().enumerate() {
-        for (x, ch) in line.chars().enumerate() {
-            if ch != '.' {
-                frequencies
-                    .entry(ch)
-                    .or_default()
-                    .push(Point::new(x as i32, y as i32));
-            }
+    for (position, &ch) in grid.iter() {
+        if ch != '.' {
+            frequencies
+                .entry(ch)
+                .or_default()
+                .push(Point::from_index(position));
         }
     }
     
@@ -30,15 +78,8 @@ fn parse_antenna_map(input: &str) -> HashMap<char, Vec<Point>> {
 }
 
 fn get_antinode(near: Point, far: Point) -> Point {
-    // Calculate the vector from near to far
-    let dx = far.x - near.x;
-    let dy = far.y - near.y;
-    
     // The antinode is at near - (far - near) = 2*near - far
-    Point::new(
-        2 * near.x - far.x,
-        2 * near.y - far.y
-    )
+    near - (far - near)
 }
 
 fn calculate_antinodes(antennas: &[Point]) -> HashSet<Point> {
@@ -65,25 +106,16 @@ fn calculate_antinodes(antennas: &[Point]) -> HashSet<Point> {
     antinodes
 }
 
-fn count_antinodes_in_bounds(input: &str) -> usize {
-    let frequencies = parse_antenna_map(input);
-    
-    // Get map dimensions
-    let height = input.lines().count() as i32;
-    let width = input.lines().next().unwrap().chars().count() as i32;
-    
+fn count_antinodes_in_bounds(map: &AntennaMap) -> usize {
     // Calculate all antinodes
     let mut all_antinodes = HashSet::new();
     
-    for antennas in frequencies.values() {
+    for antennas in map.frequencies.values() {
         let antinodes = calculate_antinodes(antennas);
         // Only keep antinodes that are within bounds
         let valid_antinodes: HashSet<_> = antinodes
             .into_iter()
-            .filter(|point| {
-                point.x >= 0 && point.x < width && 
-                point.y >= 0 && point.y < height
-            })
+            .filter(|&point| map.contains(point))
             .collect();
             
         all_antinodes.extend(valid_antinodes);
@@ -92,61 +124,54 @@ fn count_antinodes_in_bounds(input: &str) -> usize {
     all_antinodes.len()
 }
 
-fn is_collinear(p1: Point, p2: Point, p3: Point) -> bool {
-    // Calculate cross product of vectors p1p2 and p1p3
-    // If cross product is 0, points are collinear
-    let cross_product = (p2.x - p1.x) * (p3.y - p1.y) - (p2.y - p1.y) * (p3.x - p1.x);
-    cross_product == 0
-}
-
-fn find_antinodes(antennas: &[Point], width: i32, height: i32) -> HashSet<Point> {
+fn find_antinodes(antennas: &[Point], grid: &Grid<char>) -> Result<HashSet<Point>> {
     let mut antinodes = HashSet::new();
-    
-    // If we have less than 2 antennas, there are no antinodes
-    if antennas.len() < 2 {
-        return antinodes;
-    }
 
-    // Check every point in bounds
-    for y in 0..height {
-        for x in 0..width {
-            let point = Point::new(x, y);
-            
-            // For each point, check if it's collinear with at least two antennas
-            for i in 0..antennas.len() {
-                for j in (i + 1)..antennas.len() {
-                    if is_collinear(point, antennas[i], antennas[j]) {
-                        antinodes.insert(point);
-                        break; // Found one collinear pair, no need to check more
-                    }
+    // Every grid point on the line through a pair is an antinode. Stepping by
+    // the pair's offset divided by its gcd visits exactly the points on the
+    // line that have whole coordinates.
+    for i in 0..antennas.len() {
+        for j in (i + 1)..antennas.len() {
+            let delta = antennas[j] - antennas[i];
+            let g = math::gcd(i64::from(delta.x), i64::from(delta.y))
+                .ok_or_else(|| AocError::puzzle(DAY, "an antenna offset overflows"))?;
+            if g == 0 {
+                let at = antennas[i];
+                return Err(AocError::puzzle(DAY, format!("two antennas are both at ({}, {})", at.x, at.y)));
+            }
+            // The gcd divides both offsets, so it fits back into an i32
+            let g = g as i32;
+            let step = Vec2::new(delta.x / g, delta.y / g);
+
+            for step in [step, -step] {
+                let mut point = antennas[i];
+                while grid.contains(point) {
+                    antinodes.insert(point);
+                    point += step;
                 }
             }
         }
     }
-    
-    antinodes
+
+    Ok(antinodes)
 }
-fn count_antinodes_in_bounds2(input: &str) -> usize {
-    let frequencies = parse_antenna_map(input);
-    
-    // Get map dimensions
-    let height = input.lines().count() as i32;
-    let width = input.lines().next().unwrap().chars().count() as i32;
-    
+
+fn count_antinodes_in_bounds2(map: &AntennaMap) -> Result<usize> {
     // Calculate all antinodes
     let mut all_antinodes = HashSet::new();
     
-    for antennas in frequencies.values() {
-        let antinodes = find_antinodes(antennas, width, height);
+    for antennas in map.frequencies.values() {
+        let antinodes = find_antinodes(antennas, &map.grid)?;
         all_antinodes.extend(antinodes);
     }
     
-    all_antinodes.len()
+    Ok(all_antinodes.len())
+}
+
+pub fn solve_part1(map: &AntennaMap) -> Result<usize> {
+    Ok(count_antinodes_in_bounds(map))
 }
 
-pub fn solve_part1() -> usize{
-    count_antinodes_in_bounds(include_str!("../inputs/day8.txt"))
+pub fn solve_part2(map: &AntennaMap) -> Result<usize> {
+    count_antinodes_in_bounds2(map)
 }
-pub fn solve_part2() -> usize{
-    count_antinodes_in_bounds2(include_str!("../inputs/day8.txt"))
-}
\ No newline at end of file
@@ -1,9 +1,39 @@
 
 
-fn compact_disk(disk: &mut Vec<Option<usize>>) {
+use crate::cancel;
+use crate::error::Result;
+use crate::event;
+use crate::parser::Parser;
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = 9;
+
+#[derive(Default)]
+pub struct Day9 {
+    lengths: Vec<u32>,
+}
+
+impl Solution for Day9 {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        self.lengths = parse_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.lengths).map(Answer::from)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        solve_part2(&self.lengths).map(Answer::from)
+    }
+}
+
+pub fn compact_disk(disk: &mut [Option<usize>]) -> Result<()> {
     let len = disk.len();
     
     for target_pos in 0..len {
+        // Each free block scans from the right, so this is quadratic
+        cancel::check(DAY)?;
         if disk[target_pos].is_none() {
             // Find the rightmost file block
             if let Some(source_pos) = (target_pos..len).rev()
@@ -17,16 +47,15 @@ fn compact_disk(disk: &mut Vec<Option<usize>>) {
             }
         }
     }
+    Ok(())
 }
 
 
-fn parse_disk_map(input: &str) -> Vec<u32> {
-    input.chars()
-        .map(|c| c.to_digit(10).unwrap())
-        .collect()
+pub fn parse_input(input: &str) -> Result<Vec<u32>> {
+    Parser::new(DAY, input).digits(input.trim())
 }
 
-fn expand_disk_map(lengths: &[u32]) -> Vec<Option<usize>> {
+pub fn expand_disk_map(lengths: &[u32]) -> Vec<Option<usize>> {
     let mut result = Vec::new();
     let mut file_id = 0;
     
@@ -94,12 +123,15 @@ fn find_free_space(disk: &[Option<usize>], start: usize, needed: usize) -> Optio
     None
 }
 
-fn compact_disk_whole_files(disk: &mut Vec<Option<usize>>) {
+pub fn compact_disk_whole_files(disk: &mut [Option<usize>]) -> Result<()> {
     let mut files = find_files(disk);
     files.sort_by_key(|f| std::cmp::Reverse(f.id));
     
-    for file in files {
+    let mut moved = 0;
+    for file in files.iter() {
+        cancel::check(DAY)?;
         if let Some(new_start) = find_free_space(disk, file.start, file.length) {
+            moved += 1;
             // Move the whole file
             for i in 0..file.length {
                 disk[new_start + i] = Some(file.id);
@@ -107,9 +139,11 @@ fn compact_disk_whole_files(disk: &mut Vec<Option<usize>>) {
             }
         }
     }
+    event!(Debug, "day9", "whole files moved", moved = moved, of = files.len());
+    Ok(())
 }
 
-fn calculate_checksum(disk: &[Option<usize>]) -> usize {
+pub fn calculate_checksum(disk: &[Option<usize>]) -> usize {
     disk.iter()
         .enumerate()
         .filter_map(|(pos, &block)| {
@@ -118,16 +152,14 @@ fn calculate_checksum(disk: &[Option<usize>]) -> usize {
         .sum()
 }
 
-pub  fn solve_part2() -> usize {
-    let lengths = parse_disk_map(include_str!("../inputs/day9.txt"));
-    let mut disk = expand_disk_map(&lengths);
-    compact_disk_whole_files(&mut disk);
-    calculate_checksum(&disk)
+pub fn solve_part2(lengths: &[u32]) -> Result<usize> {
+    let mut disk = expand_disk_map(lengths);
+    compact_disk_whole_files(&mut disk)?;
+    Ok(calculate_checksum(&disk))
 }
 
-pub fn solve_part1() -> usize {
-    let lengths = parse_disk_map(include_str!("../inputs/day9.txt"));
-    let mut disk = expand_disk_map(&lengths);
-    compact_disk(&mut disk);
-    calculate_checksum(&disk)
-}
\ No newline at end of file
+pub fn solve_part1(lengths: &[u32]) -> Result<usize> {
+    let mut disk = expand_disk_map(lengths);
+    compact_disk(&mut disk)?;
+    Ok(calculate_checksum(&disk))
+}
@@ -0,0 +1,90 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Everything that can go wrong between reading a puzzle input and answering it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AocError {
+    /// The input text is malformed. `line` and `column` are 1-based and point
+    /// at the start of `text`.
+    Parse {
+        day: u8,
+        line: usize,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    /// The input parsed but does not describe a puzzle that can be solved,
+    /// e.g. a guard map without a guard.
+    Puzzle { day: u8, message: String },
+    /// The solver gave up because its time budget ran out; see
+    /// [`crate::cancel`].
+    Timeout { day: u8 },
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    /// A parse error for `text`, which must be a slice of `input` so that its
+    /// line and column can be worked out.
+    pub fn at(day: u8, input: &str, text: &str, message: impl Into<String>) -> Self {
+        let (line, column) = position(input, text);
+        AocError::Parse {
+            day,
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn puzzle(day: u8, message: impl Into<String>) -> Self {
+        AocError::Puzzle {
+            day,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Parse {
+                day,
+                line,
+                column,
+                text,
+                message,
+            } => write!(
+                f,
+                "day {}, line {}, column {}: {}: `{}`",
+                day, line, column, message, text
+            ),
+            AocError::Puzzle { day, message } => write!(f, "day {}: {}", day, message),
+            AocError::Timeout { day } => write!(f, "day {}: ran out of time", day),
+        }
+    }
+}
+
+impl Error for AocError {}
+
+/// Parses `text` (a slice of `input`) as a number, reporting where it failed.
+pub fn parse_number<T: FromStr>(day: u8, input: &str, text: &str) -> Result<T> {
+    text.trim()
+        .parse()
+        .map_err(|_| AocError::at(day, input, text, "invalid number"))
+}
+
+// Line and column of `text` within `input`, or (0, 0) if it is not a slice of it.
+fn position(input: &str, text: &str) -> (usize, usize) {
+    let start = input.as_ptr() as usize;
+    let offset = (text.as_ptr() as usize).wrapping_sub(start);
+    let Some(before) = input.get(..offset) else {
+        return (0, 0);
+    };
+
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
@@ -0,0 +1,177 @@
+//! Downloads puzzle inputs from the puzzle server.
+//!
+//! `http://` URLs are fetched directly over a `TcpStream`, which is all a
+//! local stand-in server needs. The real site is `https://`, and rather than
+//! pull a TLS stack into the crate those requests go through `curl`.
+
+use std::env;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the `session` cookie of a logged-in user.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable overriding [`DEFAULT_BASE_URL`].
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Fetches `<base_url>/<year>/day/<day>/input` as the user the session
+/// token belongs to.
+#[derive(Clone)]
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+}
+
+impl Fetcher {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Fetcher {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into().trim().to_string(),
+        }
+    }
+
+    /// A fetcher configured from `AOC_SESSION` and `AOC_BASE_URL`, or `None`
+    /// when there is no session token.
+    pub fn from_env() -> Option<Self> {
+        let session = env::var(SESSION_VAR).ok().filter(|s| !s.trim().is_empty())?;
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Some(Fetcher::new(base_url, session))
+    }
+
+    pub fn url(&self, year: u16, day: u8) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, year, day)
+    }
+
+    /// Downloads one input. A response that is not `200 OK`, or whose body
+    /// is empty or cut short, is an error.
+    pub fn fetch(&self, year: u16, day: u8) -> io::Result<String> {
+        let url = self.url(year, day);
+        let body = if let Some(rest) = url.strip_prefix("http://") {
+            self.get_http(rest)
+        } else if url.starts_with("https://") {
+            self.get_curl(&url)
+        } else {
+            Err(invalid_input(format!("unsupported URL scheme in {}", url)))
+        }
+        .map_err(|err| io::Error::new(err.kind(), format!("GET {}: {}", url, err)))?;
+
+        // The server always ends an input with a newline
+        if body.trim().is_empty() || !body.ends_with('\n') {
+            return Err(invalid_data(format!("GET {}: the response looks truncated", url)));
+        }
+        Ok(body)
+    }
+
+    // `rest` is the URL after `http://`
+    fn get_http(&self, rest: &str) -> io::Result<String> {
+        let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+        let path = if path.is_empty() { "/" } else { path };
+        let address = if authority.contains(':') { authority.to_string() } else { format!("{}:80", authority) };
+        let address = address
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| invalid_input(format!("cannot resolve {}", authority)))?;
+
+        let mut stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: {}\r\nCookie: session={}\r\nUser-Agent: {}\r\nConnection: close\r\n\r\n",
+            path,
+            authority,
+            self.session,
+            user_agent()
+        )?;
+
+        read_response(BufReader::new(stream))
+    }
+
+    // The token goes to curl on stdin so it never shows up in a process list
+    fn get_curl(&self, url: &str) -> io::Result<String> {
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--max-time", "30", "--user-agent", &user_agent()])
+            .args(["--header", "@-", url])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| io::Error::new(err.kind(), format!("cannot run curl: {}", err)))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "Cookie: session={}", self.session)?;
+        }
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(io::Error::other(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+        }
+        String::from_utf8(output.stdout).map_err(|_| invalid_data("the response is not UTF-8".to_string()))
+    }
+}
+
+fn user_agent() -> String {
+    format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+}
+
+/// Reads an HTTP/1.1 response and returns its body, which must come with a
+/// `200` status. Handles `Content-Length`, chunked and read-to-close bodies.
+fn read_response(mut reader: impl BufRead) -> io::Result<String> {
+    let mut status = String::new();
+    reader.read_line(&mut status)?;
+    let code = status.split_whitespace().nth(1).unwrap_or_default();
+    if code != "200" {
+        let reason = status.trim().split_once(' ').map_or("no response", |(_, reason)| reason);
+        return Err(io::Error::other(format!("server answered {}", reason)));
+    }
+
+    let (mut length, mut chunked) = (None, false);
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            length = Some(value.parse::<usize>().map_err(|_| invalid_data(format!("bad Content-Length {}", value)))?);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            chunked = value.eq_ignore_ascii_case("chunked");
+        }
+    }
+
+    let mut body = Vec::new();
+    if chunked {
+        loop {
+            let mut size = String::new();
+            reader.read_line(&mut size)?;
+            let size = usize::from_str_radix(size.trim().split(';').next().unwrap_or_default(), 16)
+                .map_err(|_| invalid_data("bad chunk size".to_string()))?;
+            if size == 0 {
+                break;
+            }
+            let start = body.len();
+            body.resize(start + size, 0);
+            reader.read_exact(&mut body[start..])?;
+            reader.read_line(&mut String::new())?;
+        }
+    } else {
+        reader.read_to_end(&mut body)?;
+        if let Some(length) = length.filter(|&length| length != body.len()) {
+            return Err(invalid_data(format!("expected {} bytes but got {}", length, body.len())));
+        }
+    }
+
+    String::from_utf8(body).map_err(|_| invalid_data("the response is not UTF-8".to_string()))
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
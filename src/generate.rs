@@ -0,0 +1,291 @@
+use std::fmt::Write;
+
+/// A small, seedable PRNG (SplitMix64) so generated inputs are reproducible.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `lo..=hi`.
+    pub fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        lo + self.next_u64() % (hi - lo + 1)
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= p
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.range(0, items.len() as u64 - 1) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.range(0, i as u64) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// The size that roughly matches a real puzzle input for `day`.
+pub fn default_size(day: u8) -> Option<usize> {
+    Some(match day {
+        1 | 2 => 1000,
+        3 => 700,
+        4 | 6 | 12 => 130,
+        5 => 200,
+        7 => 850,
+        8 | 10 => 50,
+        9 => 20_000,
+        11 => 8,
+        13 => 320,
+        _ => return None,
+    })
+}
+
+/// Generates a random but valid puzzle input for `day`. What `size` means
+/// depends on the day: lines, grid side, updates, disk map digits or machines.
+pub fn generate(day: u8, size: usize, rng: &mut Rng) -> Option<String> {
+    let size = size.max(1);
+    Some(match day {
+        1 => location_lists(size, rng),
+        2 => reports(size, rng),
+        3 => corrupted_memory(size, rng),
+        4 => word_search(size, rng),
+        5 => print_queue(size, rng),
+        6 => guard_map(size, rng),
+        7 => equations(size, rng),
+        8 => antenna_map(size, rng),
+        9 => disk_map(size, rng),
+        10 => topographic_map(size, rng),
+        11 => stones(size, rng),
+        12 => garden(size, rng),
+        13 => claw_machines(size, rng),
+        _ => return None,
+    })
+}
+
+fn location_lists(lines: usize, rng: &mut Rng) -> String {
+    let left: Vec<u64> = (0..lines).map(|_| rng.range(10_000, 99_999)).collect();
+    let mut out = String::new();
+    for &l in &left {
+        // Reuse left IDs on the right now and then so similarity scores are non-zero
+        let r = if rng.chance(0.3) { rng.pick(&left) } else { rng.range(10_000, 99_999) };
+        let _ = writeln!(out, "{}   {}", l, r);
+    }
+    out
+}
+
+fn reports(lines: usize, rng: &mut Rng) -> String {
+    let mut out = String::new();
+    for _ in 0..lines {
+        let len = rng.range(5, 8);
+        let increasing = rng.chance(0.5);
+        let mut level = rng.range(10, 90) as i64;
+        let mut levels = Vec::new();
+        for _ in 0..len {
+            levels.push(level.to_string());
+            // Mostly safe steps, with the occasional bad one
+            let step = if rng.chance(0.9) { rng.range(1, 3) } else { rng.range(0, 6) } as i64;
+            level += if increasing { step } else { -step };
+        }
+        let _ = writeln!(out, "{}", levels.join(" "));
+    }
+    out
+}
+
+fn corrupted_memory(instructions: usize, rng: &mut Rng) -> String {
+    const NOISE: &[&str] = &["!", "@", "#", "%", "^", "&", "*", "(", ")", "[", "]", "mul", "mul(", ",", " ", "what()", "from()"];
+
+    let mut out = String::new();
+    for _ in 0..instructions {
+        for _ in 0..rng.range(0, 4) {
+            out.push_str(rng.pick(NOISE));
+        }
+        match rng.range(0, 9) {
+            0 => out.push_str("do()"),
+            1 => out.push_str("don't()"),
+            2 => {
+                // A near miss that must not count
+                let _ = write!(out, "mul({},{}]", rng.range(1, 999), rng.range(1, 999));
+            }
+            _ => {
+                let _ = write!(out, "mul({},{})", rng.range(1, 999), rng.range(1, 999));
+            }
+        }
+    }
+    out.push('\n');
+    out
+}
+
+fn word_search(side: usize, rng: &mut Rng) -> String {
+    char_grid(side, rng, |rng, _, _| rng.pick(&['X', 'M', 'A', 'S']))
+}
+
+fn print_queue(updates: usize, rng: &mut Rng) -> String {
+    // Every pair of pages is ordered by one random permutation, so the rules
+    // can never contain a cycle
+    let mut pages: Vec<u64> = (10..=99).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(49);
+
+    let mut out = String::new();
+    for i in 0..pages.len() {
+        for j in i + 1..pages.len() {
+            let _ = writeln!(out, "{}|{}", pages[i], pages[j]);
+        }
+    }
+    out.push('\n');
+
+    for _ in 0..updates {
+        let mut update = pages.clone();
+        rng.shuffle(&mut update);
+        // Odd lengths so every update has a middle page
+        update.truncate(rng.range(2, 11) as usize * 2 + 1);
+        if rng.chance(0.5) {
+            update.sort_by_key(|page| pages.iter().position(|p| p == page));
+        }
+        let update: Vec<String> = update.iter().map(u64::to_string).collect();
+        let _ = writeln!(out, "{}", update.join(","));
+    }
+    out
+}
+
+fn guard_map(side: usize, rng: &mut Rng) -> String {
+    let guard = (rng.range(0, side as u64 - 1) as usize, rng.range(0, side as u64 - 1) as usize);
+    char_grid(side, rng, |rng, row, col| {
+        if (row, col) == guard {
+            '^'
+        } else if rng.chance(0.04) {
+            '#'
+        } else {
+            '.'
+        }
+    })
+}
+
+fn equations(lines: usize, rng: &mut Rng) -> String {
+    // Test values have to fit the solver's i64, and so does their sum over
+    // every line
+    let max_value = i64::MAX as u64 / lines.max(1) as u64;
+    let mut out = String::new();
+    for _ in 0..lines {
+        let numbers: Vec<u64> = (0..rng.range(2, 10)).map(|_| rng.range(1, 99)).collect();
+        // About half the equations are built to be solvable
+        let test_value = if rng.chance(0.5) {
+            numbers[1..].iter().fold(numbers[0], |acc, &n| {
+                let next = match rng.range(0, 2) {
+                    0 => acc.checked_add(n),
+                    1 => acc.checked_mul(n),
+                    _ => format!("{}{}", acc, n).parse().ok(),
+                };
+                next.filter(|&v| v <= max_value).unwrap_or(acc)
+            })
+        } else {
+            rng.range(1, 1_000_000_000).min(max_value)
+        };
+        let numbers: Vec<String> = numbers.iter().map(u64::to_string).collect();
+        let _ = writeln!(out, "{}: {}", test_value, numbers.join(" "));
+    }
+    out
+}
+
+fn antenna_map(side: usize, rng: &mut Rng) -> String {
+    const FREQUENCIES: &[char] = &['0', '1', '7', 'a', 'A', 'b', 'Z'];
+    char_grid(side, rng, |rng, _, _| {
+        if rng.chance(0.03) {
+            rng.pick(FREQUENCIES)
+        } else {
+            '.'
+        }
+    })
+}
+
+fn disk_map(digits: usize, rng: &mut Rng) -> String {
+    let mut out: String = (0..digits)
+        .map(|i| {
+            // Files are never empty; free space may be
+            let length = if i % 2 == 0 { rng.range(1, 9) } else { rng.range(0, 9) };
+            char::from(b'0' + length as u8)
+        })
+        .collect();
+    out.push('\n');
+    out
+}
+
+fn topographic_map(side: usize, rng: &mut Rng) -> String {
+    // Heights climb along the diagonals, with some noise, so there are trails
+    let mut out = String::with_capacity(side * (side + 1));
+    for row in 0..side {
+        for col in 0..side {
+            let height = if rng.chance(0.2) { rng.range(0, 9) as usize } else { (row + col) % 10 };
+            out.push(char::from(b'0' + height as u8));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn stones(count: usize, rng: &mut Rng) -> String {
+    let stones: Vec<String> = (0..count).map(|_| rng.range(0, 999_999).to_string()).collect();
+    stones.join(" ") + "\n"
+}
+
+fn garden(side: usize, rng: &mut Rng) -> String {
+    // Copy a neighbouring plant most of the time so regions form
+    let mut plants = vec![vec!['A'; side]; side];
+    for row in 0..side {
+        for col in 0..side {
+            plants[row][col] = match rng.range(0, 9) {
+                0..=3 if row > 0 => plants[row - 1][col],
+                4..=7 if col > 0 => plants[row][col - 1],
+                _ => char::from(b'A' + rng.range(0, 25) as u8),
+            };
+        }
+    }
+    plants.iter().map(|row| row.iter().collect::<String>() + "\n").collect()
+}
+
+fn claw_machines(machines: usize, rng: &mut Rng) -> String {
+    let mut out = String::new();
+    for i in 0..machines {
+        let (ax, ay) = (rng.range(10, 99), rng.range(10, 99));
+        let (bx, by) = (rng.range(10, 99), rng.range(10, 99));
+        // About half the prizes are reachable
+        let (px, py) = if rng.chance(0.5) {
+            let (a, b) = (rng.range(0, 100), rng.range(0, 100));
+            (a * ax + b * bx, a * ay + b * by)
+        } else {
+            (rng.range(1_000, 20_000), rng.range(1_000, 20_000))
+        };
+        if i > 0 {
+            out.push('\n');
+        }
+        let _ = writeln!(out, "Button A: X+{}, Y+{}", ax, ay);
+        let _ = writeln!(out, "Button B: X+{}, Y+{}", bx, by);
+        let _ = writeln!(out, "Prize: X={}, Y={}", px, py);
+    }
+    out
+}
+
+fn char_grid(side: usize, rng: &mut Rng, mut cell: impl FnMut(&mut Rng, usize, usize) -> char) -> String {
+    let mut out = String::with_capacity(side * (side + 1));
+    for row in 0..side {
+        for col in 0..side {
+            out.push(cell(rng, row, col));
+        }
+        out.push('\n');
+    }
+    out
+}
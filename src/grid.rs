@@ -0,0 +1,158 @@
+use std::iter;
+use std::ops::{Index, IndexMut};
+
+use crate::error::Result;
+use crate::geom::{Direction, Point, Vec2};
+use crate::parser::Parser;
+
+/// A rectangular grid stored row by row in a single `Vec`.
+///
+/// Positions are `(x, y)`: column, then row, with `(0, 0)` at the top left.
+/// Indexing with a `(usize, usize)` panics outside the grid; [`Grid::get`]
+/// takes a signed [`Point`] so callers can step off an edge and find out.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Parses one cell per character, one row per line. Rows must all be the
+    /// same width and the grid must not be empty; `cell` returns `None` for a
+    /// character it does not accept.
+    pub fn parse(day: u8, input: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Self> {
+        Parser::new(day, input).grid(input, cell)
+    }
+
+    /// A grid from cells given row by row; `width` must be non-zero and
+    /// divide the number of cells.
+    pub(crate) fn from_cells(width: usize, cells: Vec<T>) -> Self {
+        debug_assert!(width > 0 && cells.len().is_multiple_of(width));
+        Grid {
+            height: cells.len() / width,
+            cells,
+            width,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.checked(point).is_some()
+    }
+
+    /// The cell at `point`, or `None` off the edge of the grid.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.checked(point).map(|position| &self[position])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.checked(point).map(|position| &mut self[position])
+    }
+
+    fn checked(&self, point: Point) -> Option<(usize, usize)> {
+        let (x, y) = (usize::try_from(point.x).ok()?, usize::try_from(point.y).ok()?);
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every position with its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The in-bounds orthogonal neighbours of `position`.
+    pub fn neighbours4(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(position, &Direction::ORTHOGONAL)
+    }
+
+    /// The in-bounds orthogonal and diagonal neighbours of `position`.
+    pub fn neighbours8(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(position, &Direction::ALL)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        position: (usize, usize),
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let point = Point::from_index(position);
+        directions.iter().filter_map(move |&direction| self.checked(point + direction))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// The cells from `start` stepping by `step` until the edge, starting
+    /// with `start` itself.
+    pub fn ray(&self, start: Point, step: Vec2) -> impl Iterator<Item = &T> + '_ {
+        iter::successors(Some(start), move |&point| Some(point + step)).map_while(|point| self.get(point))
+    }
+
+    /// The cells from `(x, y)` down and to the right.
+    pub fn diagonal(&self, x: usize, y: usize) -> impl Iterator<Item = &T> + '_ {
+        self.ray(Point::from_index((x, y)), Direction::SouthEast.offset())
+    }
+
+    /// The cells from `(x, y)` down and to the left.
+    pub fn anti_diagonal(&self, x: usize, y: usize) -> impl Iterator<Item = &T> + '_ {
+        self.ray(Point::from_index((x, y)), Direction::SouthWest.offset())
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// The first position, row by row, holding `value`.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)> {
+        self.find_all(value).next()
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.iter().filter(move |(_, cell)| *cell == value).map(|(position, _)| position)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width, "x {} out of bounds for width {}", x, self.width);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width, "x {} out of bounds for width {}", x, self.width);
+        &mut self.cells[y * self.width + x]
+    }
+}
@@ -0,0 +1,234 @@
+use std::env;
+use std::fs::{self, read_to_string};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::event;
+use crate::fetch::{Fetcher, SESSION_VAR};
+
+pub const INPUTS_DIR: &str = "inputs";
+
+/// The puzzle year, used to key cached and downloaded inputs.
+pub const YEAR: u16 = 2024;
+
+/// Environment variable overriding where downloaded inputs are kept.
+pub const CACHE_DIR_VAR: &str = "AOC_CACHE_DIR";
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `inputs/dayN.txt`, or the user's [`InputCache`] when that file does
+    /// not exist or is empty, as `new` leaves it
+    Default,
+    /// `inputs/<profile>/dayN.txt`, one directory per team member
+    Profile(String),
+    /// An explicit file, used as-is whatever the day
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Interprets a `--input` argument: `-` means stdin, anything else is a path.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::Path(PathBuf::from(arg))
+        }
+    }
+
+    /// The file this source resolves to for `day`, if it is file-backed.
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        self.path_in(Path::new(INPUTS_DIR), day)
+    }
+
+    /// [`InputSource::path`] with the default and profile inputs under
+    /// `inputs` rather than `./inputs`.
+    pub fn path_in(&self, inputs: &Path, day: u8) -> Option<PathBuf> {
+        let file = format!("day{}.txt", day);
+        match self {
+            InputSource::Default => Some(inputs.join(file)),
+            InputSource::Profile(name) => Some(inputs.join(name).join(file)),
+            InputSource::Path(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// The file `read` takes the input for `day` from: [`InputSource::path`],
+    /// or the cached copy when the default file does not exist or is empty.
+    pub fn resolved_path(&self, day: u8) -> io::Result<Option<PathBuf>> {
+        Ok(self.resolved_path_in(Path::new(INPUTS_DIR), &InputCache::from_env()?, day))
+    }
+
+    /// [`InputSource::resolved_path`] with the inputs under `inputs` and
+    /// `cache` to fall back on.
+    pub fn resolved_path_in(&self, inputs: &Path, cache: &InputCache, day: u8) -> Option<PathBuf> {
+        match self.path_in(inputs, day) {
+            Some(path) if *self == InputSource::Default && is_missing_or_empty(&path) => Some(cache.path(YEAR, day)),
+            path => path,
+        }
+    }
+
+    /// The name answers are filed under: the profile, the file stem for an
+    /// explicit path, or `default`/`stdin`.
+    pub fn name(&self) -> String {
+        match self {
+            InputSource::Default => "default".to_string(),
+            InputSource::Profile(name) => name.clone(),
+            InputSource::Path(path) => path
+                .file_stem()
+                .map_or_else(|| path.display().to_string(), |stem| stem.to_string_lossy().into_owned()),
+            InputSource::Stdin => "stdin".to_string(),
+        }
+    }
+
+    /// Whether the source names one specific input rather than one per day.
+    pub fn is_single(&self) -> bool {
+        matches!(self, InputSource::Path(_) | InputSource::Stdin)
+    }
+
+    /// Reads the input for `day`, refusing one that is empty.
+    pub fn read(&self, day: u8) -> io::Result<String> {
+        self.read_in(Path::new(INPUTS_DIR), &InputCache::from_env()?, day)
+    }
+
+    /// [`InputSource::read`] with the inputs under `inputs` and `cache` to
+    /// fall back on.
+    pub fn read_in(&self, inputs: &Path, cache: &InputCache, day: u8) -> io::Result<String> {
+        match self.path_in(inputs, day) {
+            Some(path) if *self == InputSource::Default && is_missing_or_empty(&path) => {
+                cache.get(YEAR, day).map_err(|err| {
+                    io::Error::new(err.kind(), format!("{} is missing or empty, and {}", path.display(), err))
+                })
+            }
+            Some(path) => read_checked(&path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                check_input(&input).map_err(|problem| invalid_data(format!("stdin: {}", problem)))?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Rejects an input that cannot be a puzzle input: one that is empty or
+/// holds NUL bytes.
+pub fn check_input(input: &str) -> Result<(), String> {
+    if input.trim().is_empty() {
+        return Err("the input is empty".to_string());
+    }
+    if input.contains('\0') {
+        return Err("the input contains NUL bytes, so it was not saved completely".to_string());
+    }
+    Ok(())
+}
+
+/// [`check_input`], and also rejects a download whose last line is cut
+/// short when every line before it has the same length, as in the grid
+/// puzzles. Only downloads are held to this: an input someone chose may well
+/// end on a short record.
+pub fn check_download(input: &str) -> Result<(), String> {
+    check_input(input)?;
+    let lines: Vec<&str> = input.trim_end().lines().collect();
+    if let [first, middle @ .., last] = &lines[..] {
+        let width = first.len();
+        if width > 1 && !middle.is_empty() && middle.iter().all(|line| line.len() == width) && last.len() < width {
+            return Err(format!("line {} is shorter than the lines before it; the file looks truncated", lines.len()));
+        }
+    }
+    Ok(())
+}
+
+/// The current user's cache directory: `$AOC_CACHE_DIR`,
+/// `$XDG_CACHE_HOME/aoc` or `~/.cache/aoc`.
+pub fn cache_dir() -> io::Result<PathBuf> {
+    let var = |name| env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+    var(CACHE_DIR_VAR)
+        .or_else(|| var("XDG_CACHE_HOME").map(|dir| dir.join("aoc")))
+        .or_else(|| var("HOME").or_else(|| var("USERPROFILE")).map(|home| home.join(".cache").join("aoc")))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("set {} to choose a cache directory", CACHE_DIR_VAR)))
+}
+
+/// Inputs kept for the current user, outside the repository, as
+/// `<dir>/<year>/dayN.txt`. Missing ones are downloaded when there is a
+/// [`Fetcher`].
+pub struct InputCache {
+    dir: PathBuf,
+    fetcher: Option<Fetcher>,
+}
+
+impl InputCache {
+    pub fn new(dir: impl Into<PathBuf>, fetcher: Option<Fetcher>) -> Self {
+        InputCache {
+            dir: dir.into(),
+            fetcher,
+        }
+    }
+
+    /// The cache in [`cache_dir`], fetching with the session in
+    /// `$AOC_SESSION` if it is set.
+    pub fn from_env() -> io::Result<Self> {
+        Ok(InputCache::new(cache_dir()?, Fetcher::from_env()))
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir.join(year.to_string()).join(format!("day{}.txt", day))
+    }
+
+    /// The cached input, downloading it first if it is missing. A cached
+    /// file that fails [`check_input`] is downloaded again when possible and
+    /// an error otherwise.
+    pub fn get(&self, year: u16, day: u8) -> io::Result<String> {
+        let path = self.path(year, day);
+        let cached = path.exists().then(|| read_checked(&path));
+        let fetcher = match (cached, &self.fetcher) {
+            (Some(Ok(input)), _) => {
+                event!(Debug, "input", "read cached input", day = day, path = path);
+                return Ok(input);
+            }
+            (Some(Err(err)), None) => return Err(err),
+            (None, None) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!(
+                        "no input for day {}: put it in {} or set {} to download it",
+                        day,
+                        path.display(),
+                        SESSION_VAR
+                    ),
+                ))
+            }
+            (_, Some(fetcher)) => fetcher,
+        };
+
+        event!(Info, "input", "downloading input", day = day, url = fetcher.url(year, day));
+        let input = fetcher.fetch(year, day)?;
+        check_download(&input).map_err(|problem| invalid_data(format!("day {} download: {}", day, problem)))?;
+
+        // Written aside and renamed, so an interrupted write never leaves a
+        // partial file behind
+        fs::create_dir_all(path.parent().unwrap_or(&self.dir))?;
+        let partial = path.with_extension("part");
+        fs::write(&partial, &input)?;
+        fs::rename(&partial, &path)?;
+        event!(Debug, "input", "cached input", day = day, path = path, bytes = input.len());
+        Ok(input)
+    }
+}
+
+// A default input file only stands in for the cached one once it holds
+// something
+fn is_missing_or_empty(path: &Path) -> bool {
+    fs::metadata(path).map_or(true, |metadata| metadata.len() == 0)
+}
+
+fn read_checked(path: &Path) -> io::Result<String> {
+    let input = read_to_string(path).map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+    check_input(&input).map_err(|problem| invalid_data(format!("{}: {}", path.display(), problem)))?;
+    Ok(input)
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,47 @@
+//! Advent of Code 2024 solutions.
+//!
+//! Each `dayN` module exposes `parse_input`, its domain types and
+//! `solve_part1`/`solve_part2`, plus a `DayN` type implementing
+//! [`Solution`] so the days can be driven generically through
+//! [`solution::DAYS`].
+
+pub mod batch;
+pub mod bench;
+pub mod cancel;
+pub mod config;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod error;
+pub mod fetch;
+pub mod generate;
+pub mod geom;
+pub mod grid;
+pub mod input;
+pub mod log;
+pub mod math;
+pub mod parser;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod report;
+pub mod results;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod serve;
+pub mod solution;
+pub mod verify;
+pub mod watch;
+
+pub use error::{AocError, Result};
+pub use solution::{Answer, Solution};
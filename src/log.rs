@@ -0,0 +1,177 @@
+//! Levelled, structured diagnostics, written to stderr so they never mix with
+//! answers. Everything is silent until switched on with `-v` or `AOC_LOG`.
+//!
+//! `AOC_LOG` takes comma-separated directives: a bare level sets the default
+//! and `target=level` sets one target, so `AOC_LOG=info,day6=trace` shows
+//! every info event plus all of day 6. Days log under `dayN`; the runner and
+//! input cache under `runner` and `input`.
+//!
+//! Events are emitted with [`event!`](crate::event), which only formats its
+//! fields when the event is enabled:
+//!
+//! ```
+//! # use aoc_2024_rs::event;
+//! let visited = 41;
+//! event!(Debug, "day6", "guard left the map", visited = visited);
+//! ```
+
+use std::fmt::{self, Debug, Write as _};
+use std::io::{self, Write as _};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::RwLock;
+
+/// Environment variable holding the filter directives.
+pub const LOG_VAR: &str = "AOC_LOG";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("unknown log level: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        f.pad(name)
+    }
+}
+
+/// Which events are shown: the most verbose level for each target, and for
+/// every other target. `None` means off.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    default: Option<Level>,
+    targets: Vec<(String, Option<Level>)>,
+}
+
+impl Filter {
+    /// The filter for `-v` given `count` times: info, then debug, then trace.
+    pub fn verbosity(count: usize) -> Self {
+        let default = match count {
+            0 => None,
+            1 => Some(Level::Info),
+            2 => Some(Level::Debug),
+            _ => Some(Level::Trace),
+        };
+        Filter { default, targets: Vec::new() }
+    }
+
+    /// Parses directives such as `debug`, `day6=trace` or `info,runner=off`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let level = |s: &str| match s.trim() {
+            "off" => Ok(None),
+            s => s.parse().map(Some),
+        };
+
+        let mut filter = Filter::default();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, value)) => filter.targets.push((target.trim().to_string(), level(value)?)),
+                None => filter.default = level(directive)?,
+            }
+        }
+        Ok(filter)
+    }
+
+    /// `self` with `other` layered on top: its default, if it sets one, and
+    /// its targets win.
+    pub fn with(mut self, other: Filter) -> Self {
+        if other.default.is_some() {
+            self.default = other.default;
+        }
+        self.targets.extend(other.targets);
+        self
+    }
+
+    pub fn level_for(&self, target: &str) -> Option<Level> {
+        // The last directive for a target wins
+        match self.targets.iter().rev().find(|(t, _)| t == target) {
+            Some(&(_, level)) => level,
+            None => self.default,
+        }
+    }
+
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        self.level_for(target).is_some_and(|max| level <= max)
+    }
+
+    fn max_level(&self) -> Option<Level> {
+        self.targets.iter().filter_map(|&(_, level)| level).chain(self.default).max()
+    }
+}
+
+static FILTER: RwLock<Filter> = RwLock::new(Filter {
+    default: None,
+    targets: Vec::new(),
+});
+// The most verbose level any target has, so disabled events cost one load
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Replaces the filter for the whole process.
+pub fn set_filter(filter: Filter) {
+    MAX_LEVEL.store(filter.max_level().map_or(0, |level| level as u8), Ordering::Relaxed);
+    if let Ok(mut current) = FILTER.write() {
+        *current = filter;
+    }
+}
+
+pub fn enabled(level: Level, target: &str) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+        && FILTER.read().is_ok_and(|filter| filter.enabled(level, target))
+}
+
+/// One event as a line: level, target, message, then `key=value` fields.
+pub fn format_event(level: Level, target: &str, message: &str, fields: &[(&str, &dyn Debug)]) -> String {
+    let mut line = format!("{:<5} {}: {}", level, target, message);
+    for (key, value) in fields {
+        let _ = write!(line, " {}={:?}", key, value);
+    }
+    line
+}
+
+/// Writes an event to stderr. Use [`event!`](crate::event), which checks
+/// [`enabled`] first.
+pub fn emit(level: Level, target: &str, message: &str, fields: &[(&str, &dyn Debug)]) {
+    let _ = writeln!(io::stderr().lock(), "{}", format_event(level, target, message, fields));
+}
+
+/// Emits a structured event: `event!(Level, "target", "message", key = value, ...)`.
+/// Values are shown with their `Debug` formatting.
+#[macro_export]
+macro_rules! event {
+    ($level:ident, $target:expr, $message:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::log::enabled($crate::log::Level::$level, $target) {
+            $crate::log::emit(
+                $crate::log::Level::$level,
+                $target,
+                &$message,
+                &[$((stringify!($key), &$value as &dyn ::std::fmt::Debug)),*],
+            );
+        }
+    };
+}
@@ -1,36 +1,540 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self as stdio, IsTerminal};
+use std::net::TcpListener;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use day13::*;
-use std::io::{self};
-
-use std::time::Instant;
-
-
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-fn main() -> io::Result<()> {
-
-    let mut input = read_input("day13.txt")?;
-    
-    // Part 1
-    let start = Instant::now();
-    let result = solve_part1(&input);
-    elapsed_time("Part 1", result, start);
-
-    // Part 2
-    let start = Instant::now();
-    let result = solve_part2(&mut input);
-    elapsed_time("Part 2", result, start);
+use aoc_2024_rs::batch;
+use aoc_2024_rs::bench::{self, BenchOptions};
+use aoc_2024_rs::cancel::Budget;
+use aoc_2024_rs::config::{self, Config};
+use aoc_2024_rs::error::AocError;
+use aoc_2024_rs::generate::{self, Rng};
+use aoc_2024_rs::input::{self, InputCache, InputSource};
+use aoc_2024_rs::log::{self, Filter};
+use aoc_2024_rs::report::{self, Format};
+use aoc_2024_rs::results::ResultCache;
+use aoc_2024_rs::runner::{self, DayRun, Job, RunOptions};
+use aoc_2024_rs::scaffold;
+use aoc_2024_rs::serve::{self, ServeOptions};
+use aoc_2024_rs::solution::{self, Answer, DAYS};
+use aoc_2024_rs::verify::{self, Answers, Status};
+use aoc_2024_rs::watch::{self, FileWatcher};
+
+const WATCH_INTERVAL: Duration = Duration::from_millis(300);
+
+const USAGE: &str = "\
+usage: aoc_2024_rs <command> <day|all> [options]
+       aoc_2024_rs serve [--port <n>] [--timeout <secs>] [--no-cache]
+
+commands:
+    run      solve both parts and print the answers; `all` runs the days in
+             parallel and prints a summary table
+    bench    time parsing and each part over repeated runs
+    verify   check the answers against answers.toml
+    generate write a random but valid puzzle input
+    fetch    download missing inputs into the user's input cache
+    cache    `cache clear [day|all]` forgets the answers stored by earlier runs
+    serve    answer `POST /day/<n>/part/<p>` requests on localhost with JSON,
+             taking the puzzle input as the body
+    new      create src/dayN.rs with stub parts, register it, and add an
+             empty input, example fixture and failing example test
+
+options:
+    --input <path>     read the puzzle input from <path> (`-` for stdin)
+    --profile <name>   read inputs/<name>/dayN.txt instead of inputs/dayN.txt
+    --inputs-dir <dir> run one day against every input in <dir> and print a
+                       matrix of input by part, checked against the answers (run)
+    --format <fmt>     print results as json, csv or markdown (run)
+    --jobs <n>         worker threads for `run all` (default: one per CPU)
+    --timeout <secs>   give up on a day after this long (run, serve)
+    --watch            rerun the day whenever its input file changes (run)
+    --no-cache         solve again even if an earlier run stored the answers (run, serve)
+    --port <n>         port to listen on (serve, default 2024)
+    --runs <n>         timed runs per phase (bench, default 10)
+    --warmup <n>       untimed runs before timing (bench, default 3)
+    --answers <path>   expected answers file (verify, --inputs-dir; default answers.toml)
+    --config <path>    per-day puzzle constants (default aoc.toml, if present)
+    --size <n>         lines, grid side or items to generate (generate)
+    --seed <n>         random seed, for reproducible inputs (generate)
+    --output <path>    write to <path> instead of stdout; a directory for `all` (generate)
+    -v, -vv, -vvv      log solver and runner events to stderr at info, debug or
+                       trace; AOC_LOG sets levels per target, e.g. day6=trace";
+
+struct Args {
+    days: Vec<u8>,
+    input: InputSource,
+    inputs_dir: Option<PathBuf>,
+    bench: BenchOptions,
+    run: RunOptions,
+    format: Option<Format>,
+    watch: bool,
+    no_cache: bool,
+    answers: PathBuf,
+    config: Option<PathBuf>,
+    size: Option<usize>,
+    seed: Option<u64>,
+    output: Option<PathBuf>,
+    verbosity: usize,
+    port: u16,
+}
+
+fn parse_args(command: &str, args: &[String]) -> Result<Args, String> {
+    let mut days = None;
+    let mut input = InputSource::Default;
+    let mut inputs_dir = None;
+    let mut bench = BenchOptions::default();
+    let mut run = RunOptions::default();
+    let mut format = None;
+    let mut watch = false;
+    let mut no_cache = false;
+    let mut answers = PathBuf::from(verify::ANSWERS_FILE);
+    let mut config = None;
+    let (mut size, mut seed, mut output) = (None, None, None);
+    let mut verbosity = 0;
+    let mut port = serve::DEFAULT_PORT;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => {
+                let value = args.next().ok_or("--input needs a value")?;
+                input = InputSource::from_arg(value);
+            }
+            "--profile" => {
+                let value = args.next().ok_or("--profile needs a value")?;
+                input = InputSource::Profile(value.clone());
+            }
+            "--inputs-dir" => {
+                let value = args.next().ok_or("--inputs-dir needs a value")?;
+                inputs_dir = Some(PathBuf::from(value));
+            }
+            "--format" => {
+                let value = args.next().ok_or("--format needs a value")?;
+                format = Some(value.parse()?);
+            }
+            "--watch" => watch = true,
+            "--no-cache" => no_cache = true,
+            "--jobs" => run.jobs = parse_count(arg, args.next())?,
+            "--port" => {
+                let value = args.next().ok_or("--port needs a value")?;
+                port = value.parse().map_err(|_| format!("invalid value for --port: {}", value))?;
+            }
+            "--timeout" => {
+                let value = args.next().ok_or("--timeout needs a value")?;
+                let secs: f64 = value
+                    .parse()
+                    .map_err(|_| format!("invalid value for --timeout: {}", value))?;
+                run.timeout = Some(Duration::try_from_secs_f64(secs).map_err(|err| format!("--timeout: {}", err))?);
+            }
+            "--runs" => bench.runs = parse_count(arg, args.next())?,
+            "--warmup" => bench.warmup = parse_count(arg, args.next())?,
+            "--answers" => {
+                let value = args.next().ok_or("--answers needs a value")?;
+                answers = PathBuf::from(value);
+            }
+            "--config" => {
+                let value = args.next().ok_or("--config needs a value")?;
+                config = Some(PathBuf::from(value));
+            }
+            "--size" => size = Some(parse_count(arg, args.next())?),
+            "--seed" => seed = Some(parse_count(arg, args.next())? as u64),
+            "--output" => {
+                let value = args.next().ok_or("--output needs a value")?;
+                output = Some(PathBuf::from(value));
+            }
+            "--verbose" => verbosity += 1,
+            flag if flag.len() > 1 && flag.strip_prefix('-').is_some_and(|vs| vs.bytes().all(|b| b == b'v')) => {
+                verbosity += flag.len() - 1
+            }
+            "all" if days.is_none() => days = Some(DAYS.iter().map(|d| d.day).collect()),
+            day if days.is_none() && !day.starts_with('-') => {
+                let day = day.parse().map_err(|_| format!("invalid day: {}", day))?;
+                days = Some(vec![day]);
+            }
+            other => return Err(format!("unexpected argument: {}", other)),
+        }
+    }
+
+    // `serve` answers for whichever day it is asked about
+    let days: Vec<u8> = match days {
+        Some(days) => days,
+        None if command == "serve" => Vec::new(),
+        None => return Err("missing day".to_string()),
+    };
+    if input.is_single() && days.len() > 1 {
+        return Err("--input names a single file and cannot be used with `all`".to_string());
+    }
+    if watch && days.len() > 1 {
+        return Err("--watch follows a single day".to_string());
+    }
+    if inputs_dir.is_some() {
+        if days.len() > 1 {
+            return Err("--inputs-dir runs a single day".to_string());
+        }
+        if input != InputSource::Default || watch {
+            return Err("--inputs-dir cannot be combined with --input, --profile or --watch".to_string());
+        }
+    }
+
+    Ok(Args {
+        days,
+        input,
+        inputs_dir,
+        bench,
+        run,
+        format,
+        watch,
+        no_cache,
+        answers,
+        config,
+        size,
+        seed,
+        output,
+        verbosity,
+        port,
+    })
+}
+
+fn parse_count(flag: &str, value: Option<&String>) -> Result<usize, String> {
+    let value = value.ok_or_else(|| format!("{} needs a value", flag))?;
+    value.parse().map_err(|_| format!("invalid value for {}: {}", flag, value))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let Some((command, rest)) = args.split_first() else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+    if command == "cache" {
+        return match clear_cache(rest) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {}\n\n{}", err, USAGE);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let args = match parse_args(command, rest) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // AOC_LOG refines whatever -v asked for
+    let directives = env::var(log::LOG_VAR).unwrap_or_default();
+    match Filter::parse(&directives) {
+        Ok(filter) => log::set_filter(Filter::verbosity(args.verbosity).with(filter)),
+        Err(err) => {
+            eprintln!("{}: {}", log::LOG_VAR, err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    // An explicit --config has to exist; aoc.toml is optional
+    let loaded = match &args.config {
+        Some(path) => Config::load(path, true),
+        None => Config::load(Path::new(config::CONFIG_FILE), false),
+    };
+    match loaded {
+        Ok(loaded) => config::set(loaded),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if command == "verify" {
+        return match verify_days(&args) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    if command == "serve" {
+        return match serve_days(&args) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    if command == "run" && args.watch {
+        return match watch_day(args.days[0], &args.input, args.run.timeout) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    if let (true, Some(dir)) = (command == "run", &args.inputs_dir) {
+        return match run_batch(args.days[0], dir, &args) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    if command == "run" && (args.days.len() > 1 || args.format.is_some()) {
+        return if run_all(&args) { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+    }
+
+    let seed = args.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    });
+    let mut rng = Rng::new(seed);
+    if command == "generate" {
+        eprintln!("seed: {}", seed);
+    }
+
+    for &day in &args.days {
+        let result = match command.as_str() {
+            "run" => run_day(day, &args),
+            "bench" => bench_day(day, &args.input, args.bench),
+            "generate" => generate_day(day, &args, &mut rng),
+            "fetch" => fetch_day(day),
+            "new" => new_day(day),
+            _ => {
+                eprintln!("unknown command: {}\n\n{}", command, USAGE);
+                return ExitCode::FAILURE;
+            }
+        };
+        if let Err(err) = result {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn run_day(day: u8, args: &Args) -> Result<(), Box<dyn Error>> {
+    let entry = DAYS
+        .iter()
+        .find(|d| d.day == day)
+        .ok_or_else(|| format!("no solution registered for day {}", day))?;
+    let input = args.input.read(day)?;
+
+    // One budget for the whole day, as in `run all`
+    let budget = Budget::new(args.run.timeout);
+    let job = Job { day, new: entry.new, input: Ok(input), cache: result_cache(args) };
+    let run = runner::run_job(job, &budget);
+
+    println!("Day {}", day);
+    let parts = [
+        ("Part 1", &run.part1, run.part1_time, run.part1_cached),
+        ("Part 2", &run.part2, run.part2_time, run.part2_cached),
+    ];
+    for (name, answer, elapsed, cached) in parts {
+        if let Some(answer) = answer {
+            print_result(name, answer, elapsed, cached);
+        }
+    }
+
+    match run.status {
+        runner::Status::Ok => Ok(()),
+        runner::Status::Error(message) => Err(message.into()),
+        runner::Status::Timeout => Err(AocError::Timeout { day }.into()),
+    }
+}
+
+// The user's result cache, unless --no-cache was given or there is nowhere
+// to keep one
+fn result_cache(args: &Args) -> Option<ResultCache> {
+    if args.no_cache {
+        return None;
+    }
+    ResultCache::from_env().ok()
+}
+
+// `cache clear [day|all]`
+fn clear_cache(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let day = match args {
+        [clear] if clear == "clear" => None,
+        [clear, all] if clear == "clear" && all == "all" => None,
+        [clear, day] if clear == "clear" => Some(day.parse::<u8>().map_err(|_| format!("invalid day: {}", day))?),
+        _ => return Err("usage: cache clear [day|all]".into()),
+    };
+    let removed = ResultCache::from_env()?.clear(day)?;
+    println!("removed {} cached answer{}", removed, if removed == 1 { "" } else { "s" });
+    Ok(())
+}
+
+fn watch_day(day: u8, source: &InputSource, timeout: Option<Duration>) -> Result<(), Box<dyn Error>> {
+    let entry = DAYS
+        .iter()
+        .find(|d| d.day == day)
+        .ok_or_else(|| format!("no solution registered for day {}", day))?;
+    let path = source.resolved_path(day)?.ok_or("--watch needs an input file, not stdin")?;
+
+    println!("watching {} (Ctrl-C to stop)", path.display());
+    let watcher = FileWatcher::new(path);
+    watch::watch(day, entry.new, source, watcher, WATCH_INTERVAL, timeout, |run, previous| {
+        println!("\n{}", watch::render(run, previous).trim_end());
+        ControlFlow::Continue(())
+    });
+    Ok(())
+}
+
+// Returns whether every day finished without an error or timeout
+fn run_all(args: &Args) -> bool {
+    let cache = result_cache(args);
+    let jobs = args
+        .days
+        .iter()
+        .filter_map(|&day| DAYS.iter().find(|d| d.day == day))
+        .map(|entry| Job {
+            day: entry.day,
+            new: entry.new,
+            input: args.input.read(entry.day).map_err(|err| err.to_string()),
+            cache: cache.clone(),
+        })
+        .collect();
+
+    let runs = runner::run_parallel(jobs, args.run);
+    match args.format {
+        Some(format) => print!("{}", report::render(format, &report::records(&runs, &args.input.name()))),
+        None => print!("{}", runner::render(&runs)),
+    }
+    runs.iter().all(|run: &DayRun| run.status == runner::Status::Ok)
+}
+
+// Returns whether every input matched its recorded answers, where it has any
+fn run_batch(day: u8, dir: &Path, args: &Args) -> Result<bool, Box<dyn Error>> {
+    let entry = DAYS
+        .iter()
+        .find(|d| d.day == day)
+        .ok_or_else(|| format!("no solution registered for day {}", day))?;
+    let inputs = batch::inputs(dir, day)?;
+    if inputs.is_empty() {
+        return Err(format!("no inputs for day {} in {}", day, dir.display()).into());
+    }
+    let answers = Answers::load(&args.answers)?;
+
+    let rows = batch::run(day, entry.new, &inputs, &answers, args.run, result_cache(args).as_ref());
+    match args.format {
+        Some(format) => {
+            let records: Vec<_> = rows
+                .iter()
+                .flat_map(|row| report::records(std::slice::from_ref(&row.run), &row.input))
+                .collect();
+            print!("{}", report::render(format, &records));
+        }
+        None => {
+            let color = stdio::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+            print!("{}", batch::render(&rows, color));
+        }
+    }
+    Ok(rows.iter().all(|row| !row.outcome().is_problem()))
+}
+
+fn serve_days(args: &Args) -> Result<(), Box<dyn Error>> {
+    // Loopback only: the solvers are for local tools, not the network
+    let listener = TcpListener::bind(("127.0.0.1", args.port))?;
+    println!("listening on http://{} (Ctrl-C to stop)", listener.local_addr()?);
+    let options = ServeOptions {
+        timeout: args.run.timeout,
+        cache: result_cache(args),
+    };
+    serve::serve(listener, options)?;
+    Ok(())
+}
+
+fn bench_day(day: u8, source: &InputSource, options: BenchOptions) -> Result<(), Box<dyn Error>> {
+    let entry = DAYS
+        .iter()
+        .find(|d| d.day == day)
+        .ok_or_else(|| format!("no solution registered for day {}", day))?;
+    let input = source.read(day)?;
+
+    let report = bench::bench_day(day, entry.new, &input, options)?;
+    print!("{}", bench::render(&report));
 
     Ok(())
-}
\ No newline at end of file
+}
+
+fn generate_day(day: u8, args: &Args, rng: &mut Rng) -> Result<(), Box<dyn Error>> {
+    let size = args
+        .size
+        .or_else(|| generate::default_size(day))
+        .ok_or_else(|| format!("no generator for day {}", day))?;
+    let input = generate::generate(day, size, rng).ok_or_else(|| format!("no generator for day {}", day))?;
+
+    match &args.output {
+        Some(dir) if args.days.len() > 1 => {
+            fs::create_dir_all(dir)?;
+            fs::write(dir.join(format!("day{}.txt", day)), input)?;
+        }
+        Some(path) => fs::write(path, input)?,
+        None => print!("{}", input),
+    }
+
+    Ok(())
+}
+
+fn fetch_day(day: u8) -> Result<(), Box<dyn Error>> {
+    let cache = InputCache::from_env()?;
+    cache.get(input::YEAR, day)?;
+    println!("day {}: {}", day, cache.path(input::YEAR, day).display());
+    Ok(())
+}
+
+fn new_day(day: u8) -> Result<(), Box<dyn Error>> {
+    for path in scaffold::scaffold(Path::new("."), day)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+// Returns whether every check passed or had no recorded answer
+fn verify_days(args: &Args) -> Result<bool, Box<dyn Error>> {
+    let answers = Answers::load(&args.answers)?;
+    let input_name = args.input.name();
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+
+    for &day in &args.days {
+        let solution = solution::solution(day)
+            .ok_or_else(|| format!("no solution registered for day {}", day))?;
+        let input = args.input.read(day)?;
+
+        for check in verify::verify_day(day, solution, &input_name, &input, &answers) {
+            println!("{}", check);
+            match check.status() {
+                Status::Pass => passed += 1,
+                Status::Fail => failed += 1,
+                Status::Unknown => unknown += 1,
+            }
+        }
+    }
+
+    println!("\n{} passed, {} failed, {} unknown", passed, failed, unknown);
+    Ok(failed == 0)
+}
+
+fn print_result(name: &str, result: &Answer, elapsed: Duration, cached: bool) {
+    let time = if cached { "cached".to_string() } else { bench::format_duration(elapsed) };
+    println!("{} time: {}", name, time);
+    println!("{} result: {}", name, result);
+}
@@ -0,0 +1,375 @@
+//! Number theory and exact linear algebra over `i64` and `i128`.
+//!
+//! Everything here is overflow-checked: a result that does not fit the
+//! integer type comes back as `None` (or [`SolveError::Overflow`]) rather than
+//! wrapping or panicking.
+
+use std::fmt;
+
+/// The integer types the functions in this module work over.
+pub trait Integer: Copy + Ord + fmt::Debug + fmt::Display {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    /// Division rounding towards negative infinity.
+    fn checked_div_floor(self, other: Self) -> Option<Self>;
+    /// The remainder that takes the sign of `other`, so `a mod m` is in `0..m`.
+    fn checked_rem_euclid(self, other: Self) -> Option<Self>;
+    fn checked_neg(self) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn checked_div_floor(self, other: Self) -> Option<Self> {
+                    let q = <$t>::checked_div(self, other)?;
+                    // Truncation rounded towards zero; step down if the signs differ and it wasn't exact
+                    if (self % other != 0) && ((self < 0) != (other < 0)) {
+                        q.checked_sub(1)
+                    } else {
+                        Some(q)
+                    }
+                }
+
+                fn checked_rem_euclid(self, other: Self) -> Option<Self> {
+                    <$t>::checked_rem_euclid(self, other)
+                }
+
+                fn checked_neg(self) -> Option<Self> {
+                    <$t>::checked_neg(self)
+                }
+            }
+        )*
+    };
+}
+
+integer!(i64, i128);
+
+fn abs<T: Integer>(n: T) -> Option<T> {
+    if n < T::ZERO {
+        n.checked_neg()
+    } else {
+        Some(n)
+    }
+}
+
+/// The greatest common divisor, never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (abs(a)?, abs(b)?);
+    while b != T::ZERO {
+        (a, b) = (b, a.checked_rem_euclid(b)?);
+    }
+    Some(a)
+}
+
+/// The least common multiple, never negative; 0 if either input is 0.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    let g = gcd(a, b)?;
+    abs(a.checked_div_floor(g)?.checked_mul(b)?)
+}
+
+/// `(g, x, y)` with `a·x + b·y = g = gcd(a, b)`.
+pub fn extended_gcd<T: Integer>(a: T, b: T) -> Option<(T, T, T)> {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r.checked_div_floor(r)?;
+        (old_r, r) = (r, old_r.checked_sub(q.checked_mul(r)?)?);
+        (old_x, x) = (x, old_x.checked_sub(q.checked_mul(x)?)?);
+        (old_y, y) = (y, old_y.checked_sub(q.checked_mul(y)?)?);
+    }
+
+    if old_r < T::ZERO {
+        Some((old_r.checked_neg()?, old_x.checked_neg()?, old_y.checked_neg()?))
+    } else {
+        Some((old_r, old_x, old_y))
+    }
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`, if `a` and `m` are coprime.
+pub fn mod_inverse<T: Integer>(a: T, m: T) -> Option<T> {
+    if m <= T::ZERO {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.checked_rem_euclid(m)?, m)?;
+    if g != T::ONE {
+        return None;
+    }
+    x.checked_rem_euclid(m)
+}
+
+/// Combines congruences `x ≡ residue (mod modulus)` into one `(residue,
+/// modulus)` pair. Moduli need not be coprime; `None` means the congruences
+/// contradict each other (or overflow).
+pub fn crt<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut result = (T::ZERO, T::ONE);
+
+    for &(residue, modulus) in congruences {
+        if modulus <= T::ZERO {
+            return None;
+        }
+        let (r1, m1) = result;
+        let r2 = residue.checked_rem_euclid(modulus)?;
+
+        // Solve r1 + m1·k ≡ r2 (mod modulus) for k
+        let (g, inverse, _) = extended_gcd(m1, modulus)?;
+        let difference = r2.checked_sub(r1)?;
+        if difference.checked_rem_euclid(g)? != T::ZERO {
+            return None;
+        }
+        let step = modulus.checked_div_floor(g)?;
+        let k = difference
+            .checked_div_floor(g)?
+            .checked_rem_euclid(step)?
+            .checked_mul(inverse.checked_rem_euclid(step)?)?
+            .checked_rem_euclid(step)?;
+
+        let combined = m1.checked_mul(step)?;
+        result = (r1.checked_add(m1.checked_mul(k)?)?.checked_rem_euclid(combined)?, combined);
+    }
+    Some(result)
+}
+
+/// The non-negative solutions of `a·x + b·y = c`, which lie evenly spaced on a
+/// line: `(x + k·step_x, y − k·step_y)` for `k` in `0..count`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonNegativeSolutions<T> {
+    /// The solution with the smallest `x`
+    pub x: T,
+    pub y: T,
+    pub step_x: T,
+    pub step_y: T,
+    pub count: T,
+}
+
+impl<T: Integer> NonNegativeSolutions<T> {
+    /// The `k`th solution, counting from the one with the smallest `x`.
+    pub fn nth(&self, k: T) -> Option<(T, T)> {
+        if k < T::ZERO || k >= self.count {
+            return None;
+        }
+        Some((
+            self.x.checked_add(k.checked_mul(self.step_x)?)?,
+            self.y.checked_sub(k.checked_mul(self.step_y)?)?,
+        ))
+    }
+
+    /// The solution with the largest `x`.
+    pub fn last(&self) -> Option<(T, T)> {
+        self.nth(self.count.checked_sub(T::ONE)?)
+    }
+}
+
+/// All solutions of `a·x + b·y = c` with `x, y ≥ 0`, for positive `a` and
+/// `b`. `None` if there are none, or if working them out overflows.
+pub fn nonnegative_solutions<T: Integer>(a: T, b: T, c: T) -> Option<NonNegativeSolutions<T>> {
+    if a <= T::ZERO || b <= T::ZERO || c < T::ZERO {
+        return None;
+    }
+    let (g, x0, _) = extended_gcd(a, b)?;
+    if c.checked_rem_euclid(g)? != T::ZERO {
+        return None;
+    }
+
+    let step_x = b.checked_div_floor(g)?;
+    let step_y = a.checked_div_floor(g)?;
+    // Scale the Bézout coefficient up to c, then take the smallest x ≥ 0
+    let x = x0
+        .checked_rem_euclid(step_x)?
+        .checked_mul(c.checked_div_floor(g)?.checked_rem_euclid(step_x)?)?
+        .checked_rem_euclid(step_x)?;
+    let remainder = c.checked_sub(a.checked_mul(x)?)?;
+    if remainder < T::ZERO {
+        return None;
+    }
+    let y = remainder.checked_div_floor(b)?;
+    let count = y.checked_div_floor(step_y)?.checked_add(T::ONE)?;
+
+    Some(NonNegativeSolutions {
+        x,
+        y,
+        step_x,
+        step_y,
+        count,
+    })
+}
+
+/// An exact fraction, kept in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio<T> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: Integer> Ratio<T> {
+    /// `None` if `denom` is zero.
+    pub fn new(numer: T, denom: T) -> Option<Self> {
+        if denom == T::ZERO {
+            return None;
+        }
+        let g = gcd(numer, denom)?;
+        let (mut numer, mut denom) = (numer.checked_div_floor(g)?, denom.checked_div_floor(g)?);
+        if denom < T::ZERO {
+            (numer, denom) = (numer.checked_neg()?, denom.checked_neg()?);
+        }
+        Some(Ratio { numer, denom })
+    }
+
+    pub fn integer(n: T) -> Self {
+        Ratio { numer: n, denom: T::ONE }
+    }
+
+    pub fn numer(&self) -> T {
+        self.numer
+    }
+
+    pub fn denom(&self) -> T {
+        self.denom
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(&self) -> Option<T> {
+        (self.denom == T::ONE).then_some(self.numer)
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        Ratio::new(
+            self.numer
+                .checked_mul(other.denom)?
+                .checked_add(other.numer.checked_mul(self.denom)?)?,
+            self.denom.checked_mul(other.denom)?,
+        )
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(Ratio {
+            numer: other.numer.checked_neg()?,
+            denom: other.denom,
+        })
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        Ratio::new(self.numer.checked_mul(other.numer)?, self.denom.checked_mul(other.denom)?)
+    }
+
+    /// `None` on division by zero, as well as on overflow.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        Ratio::new(self.numer.checked_mul(other.denom)?, self.denom.checked_mul(other.numer)?)
+    }
+}
+
+impl<T: Integer> fmt::Display for Ratio<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom == T::ONE {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+/// Why a linear system could not be solved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    /// The matrix has no inverse: no solution, or infinitely many.
+    Singular,
+    /// There is a unique solution but it is not all integers.
+    NotIntegral,
+    Overflow,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Singular => write!(f, "the system has no unique solution"),
+            SolveError::NotIntegral => write!(f, "the solution is not integral"),
+            SolveError::Overflow => write!(f, "arithmetic overflow"),
+        }
+    }
+}
+
+/// Solves `m · [x, y] = rhs` exactly by Cramer's rule.
+pub fn solve_2x2<T: Integer>(m: [[T; 2]; 2], rhs: [T; 2]) -> Result<[Ratio<T>; 2], SolveError> {
+    let cross = |a: T, b: T, c: T, d: T| a.checked_mul(d)?.checked_sub(b.checked_mul(c)?);
+
+    let det = cross(m[0][0], m[0][1], m[1][0], m[1][1]).ok_or(SolveError::Overflow)?;
+    if det == T::ZERO {
+        return Err(SolveError::Singular);
+    }
+    let x = cross(rhs[0], m[0][1], rhs[1], m[1][1]).ok_or(SolveError::Overflow)?;
+    let y = cross(m[0][0], rhs[0], m[1][0], rhs[1]).ok_or(SolveError::Overflow)?;
+
+    let ratio = |n| Ratio::new(n, det).ok_or(SolveError::Overflow);
+    Ok([ratio(x)?, ratio(y)?])
+}
+
+/// Solves the square system `m · x = rhs` exactly, by Gaussian elimination
+/// over fractions. Meant for small systems.
+pub fn solve_rational<T: Integer>(m: &[Vec<T>], rhs: &[T]) -> Result<Vec<Ratio<T>>, SolveError> {
+    let n = rhs.len();
+    if m.len() != n || m.iter().any(|row| row.len() != n) {
+        return Err(SolveError::Singular);
+    }
+
+    // Augmented matrix [m | rhs]
+    let mut rows: Vec<Vec<Ratio<T>>> = m
+        .iter()
+        .zip(rhs)
+        .map(|(row, &r)| row.iter().chain([&r]).map(|&v| Ratio::integer(v)).collect())
+        .collect();
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .find(|&r| rows[r][col].numer() != T::ZERO)
+            .ok_or(SolveError::Singular)?;
+        rows.swap(col, pivot);
+
+        for r in 0..n {
+            if r == col || rows[r][col].numer() == T::ZERO {
+                continue;
+            }
+            let factor = rows[r][col].checked_div(rows[col][col]).ok_or(SolveError::Overflow)?;
+            let pivot_row = rows[col].clone();
+            for (cell, &pivot) in rows[r].iter_mut().zip(&pivot_row).skip(col) {
+                let scaled = pivot.checked_mul(factor).ok_or(SolveError::Overflow)?;
+                *cell = cell.checked_sub(scaled).ok_or(SolveError::Overflow)?;
+            }
+        }
+    }
+
+    (0..n)
+        .map(|i| rows[i][n].checked_div(rows[i][i]).ok_or(SolveError::Overflow))
+        .collect()
+}
+
+/// Like [`solve_rational`], but only accepts a solution in whole numbers.
+pub fn solve_integer<T: Integer>(m: &[Vec<T>], rhs: &[T]) -> Result<Vec<T>, SolveError> {
+    solve_rational(m, rhs)?
+        .iter()
+        .map(|v| v.to_integer().ok_or(SolveError::NotIntegral))
+        .collect()
+}
@@ -0,0 +1,203 @@
+//! Small building blocks for reading puzzle inputs.
+//!
+//! A [`Parser`] remembers the whole input so that every error can say where
+//! it happened. Its methods take a piece of that input and either split it
+//! into smaller pieces or turn it into a value, and the ones that produce
+//! several values take a closure for the item, so they nest:
+//!
+//! ```
+//! # use aoc_2024_rs::parser::Parser;
+//! let input = "1,2,3\n4,5\n";
+//! let p = Parser::new(0, input);
+//! let rows: Vec<Vec<u32>> = p.lines(input, |line| p.list(line, ",", |n| p.integer(n))).unwrap();
+//! assert_eq!(rows, [vec![1, 2, 3], vec![4, 5]]);
+//! ```
+//!
+//! Every piece handed to a closure is a slice of the original input, so the
+//! positions stay right however deep the nesting goes.
+
+use std::str::FromStr;
+
+use crate::error::{parse_number, AocError, Result};
+use crate::grid::Grid;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    day: u8,
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(day: u8, input: &'a str) -> Self {
+        Parser { day, input }
+    }
+
+    /// An error pointing at `text`, which must be a slice of the input.
+    pub fn error(&self, text: &'a str, message: impl Into<String>) -> AocError {
+        AocError::at(self.day, self.input, text, message)
+    }
+
+    /// An integer, ignoring surrounding whitespace.
+    pub fn integer<T: FromStr>(&self, text: &'a str) -> Result<T> {
+        parse_number(self.day, self.input, text)
+    }
+
+    /// Integers separated by whitespace.
+    pub fn integers<T: FromStr>(&self, text: &'a str) -> Result<Vec<T>> {
+        text.split_whitespace().map(|n| self.integer(n)).collect()
+    }
+
+    /// One digit per character, e.g. `2333133121`.
+    pub fn digits(&self, text: &'a str) -> Result<Vec<u32>> {
+        text.char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10)
+                    .ok_or_else(|| self.error(&text[i..i + c.len_utf8()], "expected a digit"))
+            })
+            .collect()
+    }
+
+    /// Items separated by `separator`, such as `75,47,61`.
+    pub fn list<T>(
+        &self,
+        text: &'a str,
+        separator: &str,
+        item: impl FnMut(&'a str) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        text.split(separator).map(item).collect()
+    }
+
+    /// One item per line, skipping blank lines.
+    pub fn lines<T>(&self, text: &'a str, item: impl FnMut(&'a str) -> Result<T>) -> Result<Vec<T>> {
+        text.lines().filter(|line| !line.trim().is_empty()).map(item).collect()
+    }
+
+    /// The blocks of lines between blank lines, without their final newline.
+    pub fn sections(&self, text: &'a str) -> Vec<&'a str> {
+        let mut sections = Vec::new();
+        let mut start = None;
+        let mut end = 0;
+        let mut offset = 0;
+
+        for line in text.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                if let Some(start) = start.take() {
+                    sections.push(&text[start..end]);
+                }
+            } else {
+                start.get_or_insert(offset);
+                end = offset + line.trim_end_matches(['\r', '\n']).len();
+            }
+            offset += line.len();
+        }
+        if let Some(start) = start {
+            sections.push(&text[start..end]);
+        }
+        sections
+    }
+
+    /// Exactly `N` sections; `what` describes them for the error.
+    pub fn sections_exact<const N: usize>(&self, text: &'a str, what: &str) -> Result<[&'a str; N]> {
+        let sections = self.sections(text);
+        let at = sections.get(N).copied().unwrap_or(text.trim());
+        sections.try_into().map_err(|_| self.error(at, format!("expected {}", what)))
+    }
+
+    /// The text either side of the first `separator`; `expected` describes
+    /// the whole shape for the error.
+    pub fn split_once(&self, text: &'a str, separator: &str, expected: &str) -> Result<(&'a str, &'a str)> {
+        text.split_once(separator)
+            .ok_or_else(|| self.error(text, format!("expected {}", expected)))
+    }
+
+    /// The text after `prefix`, which must be there.
+    pub fn strip_prefix(&self, text: &'a str, prefix: &str) -> Result<&'a str> {
+        text.strip_prefix(prefix)
+            .ok_or_else(|| self.error(text, format!("expected `{}`", prefix)))
+    }
+
+    /// An integer after a key and a separator: `X+42` or `X=18849`.
+    pub fn key_value<T: FromStr>(&self, text: &'a str, key: &str, separator: char) -> Result<T> {
+        let value = text
+            .trim()
+            .strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(separator))
+            .ok_or_else(|| self.error(text, format!("expected `{}{}<number>`", key, separator)))?;
+        self.integer(value)
+    }
+
+    /// A character grid, one row per line; `cell` returns `None` for a
+    /// character it does not accept.
+    pub fn grid<T>(&self, text: &'a str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>> {
+        let mut cells = Vec::with_capacity(text.len());
+        let mut width = None;
+
+        for line in text.trim_end_matches(['\r', '\n']).lines() {
+            let before = cells.len();
+            for (i, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| self.error(&line[i..i + c.len_utf8()], "unexpected character"))?;
+                cells.push(value);
+            }
+            let row_width = cells.len() - before;
+            if *width.get_or_insert(row_width) != row_width {
+                return Err(self.error(line, "row length differs from the first row"));
+            }
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid::from_cells(width, cells)),
+            _ => Err(AocError::puzzle(self.day, "the grid is empty")),
+        }
+    }
+}
+
+/// Reads through text from left to right, for inputs that are scanned for
+/// patterns rather than split into fields.
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Cursor { rest: text }
+    }
+
+    /// The text not yet read.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    /// Moves past `literal` if the text starts with it.
+    pub fn eat(&mut self, literal: &str) -> bool {
+        match self.rest.strip_prefix(literal) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Between one and `max` ASCII digits, as many as there are.
+    pub fn digits(&mut self, max: usize) -> Option<&'a str> {
+        let len = self.rest.bytes().take(max).take_while(u8::is_ascii_digit).count();
+        if len == 0 {
+            return None;
+        }
+        let (digits, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Some(digits)
+    }
+
+    /// Moves past one character.
+    pub fn skip(&mut self) {
+        let mut chars = self.rest.chars();
+        chars.next();
+        self.rest = chars.as_str();
+    }
+}
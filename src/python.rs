@@ -0,0 +1,769 @@
+//! Python bindings, built with the `python` feature: an extension module
+//! named `aoc_2024_rs` for exploring puzzle variants from Python.
+//!
+//! Each day is a submodule with the same `parse_input`, `solve_part1` and
+//! `solve_part2` as its Rust module. Plain data crosses as lists and tuples;
+//! the richer domain types, such as [`day13::Machine`](crate::day13::Machine),
+//! [`day12::Field`](crate::day12::Field) and its regions, and day 9's disk
+//! layout, are classes. The configurable days take their `aoc.toml` keys as
+//! keyword arguments, checked the same way:
+//!
+//! ```python
+//! from aoc_2024_rs import day11
+//! stones = day11.parse_input("125 17")
+//! day11.solve_part1(stones, blinks=25)
+//! ```
+//!
+//! Solvers run with the GIL released, so other Python threads carry on.
+
+use std::collections::HashMap;
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyIndexError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use toml::{Table, Value};
+
+use crate::config::Config;
+use crate::error;
+use crate::geom::Point;
+use crate::grid;
+use crate::solution::{Answer, DAYS};
+
+create_exception!(
+    aoc_2024_rs,
+    AocError,
+    PyValueError,
+    "An input that does not parse, or does not describe a puzzle that can be solved."
+);
+
+impl From<error::AocError> for PyErr {
+    fn from(err: error::AocError) -> PyErr {
+        AocError::new_err(err.to_string())
+    }
+}
+
+fn xy<T>(point: Point<T>) -> (T, T) {
+    (point.x, point.y)
+}
+
+/// The config with `kwargs` set in the `[section]` table, checked as if they
+/// had been read from `aoc.toml`.
+fn config(section: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Config> {
+    let mut table = Table::new();
+    for (key, value) in kwargs.into_iter().flatten() {
+        let key: String = key.extract()?;
+        let value = if let Ok(n) = value.extract::<i64>() {
+            Value::Integer(n)
+        } else if let Ok(items) = value.extract::<Vec<String>>() {
+            Value::Array(items.into_iter().map(Value::String).collect())
+        } else {
+            return Err(PyTypeError::new_err(format!("{}: expected an integer or a list of strings", key)));
+        };
+        table.insert(key, value);
+    }
+    let mut file = Table::new();
+    file.insert(section.to_string(), Value::Table(table));
+    Config::parse(&file.to_string()).map_err(PyValueError::new_err)
+}
+
+fn answer(py: Python<'_>, answer: Answer) -> PyResult<Py<PyAny>> {
+    Ok(match answer {
+        Answer::Number(n) => n.into_pyobject(py)?.into_any().unbind(),
+        Answer::Text(s) => s.into_pyobject(py)?.into_any().unbind(),
+        Answer::Unsolved => py.None(),
+    })
+}
+
+/// The days with a registered solution.
+#[pyfunction]
+fn days() -> Vec<u32> {
+    // As a list; a `Vec<u8>` would cross as `bytes`
+    DAYS.iter().map(|entry| u32::from(entry.day)).collect()
+}
+
+/// Solves one part of a day as `run` does: an `int`, a `str`, or `None` while
+/// the part is unsolved.
+#[pyfunction]
+fn solve(py: Python<'_>, day: u8, part: u8, input: &str) -> PyResult<Py<PyAny>> {
+    let entry = DAYS
+        .iter()
+        .find(|entry| entry.day == day)
+        .ok_or_else(|| PyValueError::new_err(format!("no solution registered for day {}", day)))?;
+    if !(1..=2).contains(&part) {
+        return Err(PyValueError::new_err(format!("day {} has no part {}", day, part)));
+    }
+    let solved = py.detach(|| {
+        let mut solution = (entry.new)();
+        solution.parse(input)?;
+        if part == 1 {
+            solution.part1()
+        } else {
+            solution.part2()
+        }
+    })?;
+    answer(py, solved)
+}
+
+/// A grid of characters, as days 4 and 6 read their input.
+#[pyclass(frozen, module = "aoc_2024_rs")]
+pub struct Grid(grid::Grid<char>);
+
+#[pymethods]
+impl Grid {
+    #[getter]
+    fn width(&self) -> usize {
+        self.0.width()
+    }
+
+    #[getter]
+    fn height(&self) -> usize {
+        self.0.height()
+    }
+
+    /// The rows as strings, top to bottom.
+    fn rows(&self) -> Vec<String> {
+        self.0.rows().map(|row| row.iter().collect()).collect()
+    }
+
+    fn __getitem__(&self, position: (i32, i32)) -> PyResult<char> {
+        let (x, y) = position;
+        self.0
+            .get(Point::new(x, y))
+            .copied()
+            .ok_or_else(|| PyIndexError::new_err(format!("({}, {}) is outside the grid", x, y)))
+    }
+
+    fn __str__(&self) -> String {
+        self.rows().join("\n")
+    }
+}
+
+mod day1 {
+    use super::*;
+
+    #[pyfunction]
+    fn parse_input(input: &str) -> PyResult<(Vec<i64>, Vec<i64>)> {
+        Ok(crate::day1::parse_input(input)?)
+    }
+
+    #[pyfunction]
+    fn solve_part1(py: Python<'_>, left: Vec<i64>, right: Vec<i64>) -> PyResult<i64> {
+        Ok(py.detach(|| crate::day1::solve_part1(&left, &right))?)
+    }
+
+    #[pyfunction]
+    fn solve_part2(py: Python<'_>, left: Vec<i64>, right: Vec<i64>) -> PyResult<i64> {
+        Ok(py.detach(|| crate::day1::solve_part2(&left, &right))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part2, m)?)
+    }
+}
+
+mod day2 {
+    use super::*;
+
+    #[pyfunction]
+    fn parse_input(input: &str) -> PyResult<Vec<Vec<i32>>> {
+        Ok(crate::day2::parse_input(input)?)
+    }
+
+    /// Takes `min_step` and `max_step` as keyword arguments.
+    #[pyfunction]
+    #[pyo3(signature = (reports, **settings))]
+    fn solve_part1(py: Python<'_>, reports: Vec<Vec<i32>>, settings: Option<&Bound<'_, PyDict>>) -> PyResult<usize> {
+        let config = config("day2", settings)?.day2;
+        Ok(py.detach(|| crate::day2::solve_part1(&reports, &config))?)
+    }
+
+    /// Takes `min_step` and `max_step` as keyword arguments.
+    #[pyfunction]
+    #[pyo3(signature = (reports, **settings))]
+    fn solve_part2(py: Python<'_>, reports: Vec<Vec<i32>>, settings: Option<&Bound<'_, PyDict>>) -> PyResult<usize> {
+        let config = config("day2", settings)?.day2;
+        Ok(py.detach(|| crate::day2::solve_part2(&reports, &config))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part2, m)?)
+    }
+}
+
+mod day3 {
+    use super::*;
+    use crate::day3::Instruction;
+
+    // Instructions cross as tuples: `("mul", x, y)`, `("do",)` and `("don't",)`
+    fn to_tuple(py: Python<'_>, instruction: &Instruction) -> PyResult<Py<PyAny>> {
+        Ok(match *instruction {
+            Instruction::Multiply(x, y) => ("mul", x, y).into_pyobject(py)?.into_any().unbind(),
+            Instruction::Do => ("do",).into_pyobject(py)?.into_any().unbind(),
+            Instruction::Dont => ("don't",).into_pyobject(py)?.into_any().unbind(),
+        })
+    }
+
+    fn from_tuple(item: &Bound<'_, PyAny>) -> PyResult<Instruction> {
+        let kind: String = item.get_item(0)?.extract()?;
+        match (kind.as_str(), item.len()?) {
+            ("mul", 3) => Ok(Instruction::Multiply(item.get_item(1)?.extract()?, item.get_item(2)?.extract()?)),
+            ("do", 1) => Ok(Instruction::Do),
+            ("don't", 1) => Ok(Instruction::Dont),
+            _ => Err(PyValueError::new_err(format!("not an instruction: {}", item.repr()?))),
+        }
+    }
+
+    fn instructions(items: Vec<Bound<'_, PyAny>>) -> PyResult<Vec<Instruction>> {
+        items.iter().map(from_tuple).collect()
+    }
+
+    #[pyfunction]
+    fn parse_input(py: Python<'_>, input: &str) -> PyResult<Vec<Py<PyAny>>> {
+        let instructions = crate::day3::parse_input(input)?;
+        instructions.iter().map(|instruction| to_tuple(py, instruction)).collect()
+    }
+
+    #[pyfunction]
+    fn solve_part1(py: Python<'_>, instructions: Vec<Bound<'_, PyAny>>) -> PyResult<i64> {
+        let instructions = self::instructions(instructions)?;
+        Ok(py.detach(|| crate::day3::solve_part1(&instructions))?)
+    }
+
+    #[pyfunction]
+    fn solve_part2(py: Python<'_>, instructions: Vec<Bound<'_, PyAny>>) -> PyResult<i64> {
+        let instructions = self::instructions(instructions)?;
+        Ok(py.detach(|| crate::day3::solve_part2(&instructions))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part2, m)?)
+    }
+}
+
+mod day4 {
+    use super::*;
+
+    #[pyfunction]
+    fn parse_input(input: &str) -> PyResult<Grid> {
+        Ok(Grid(crate::day4::parse_input(input)?))
+    }
+
+    #[pyfunction]
+    fn solve_part1(py: Python<'_>, grid: &Bound<'_, Grid>) -> PyResult<usize> {
+        let grid = grid.get();
+        Ok(py.detach(|| crate::day4::solve_part1(&grid.0))?)
+    }
+
+    #[pyfunction]
+    fn solve_part2(py: Python<'_>, grid: &Bound<'_, Grid>) -> PyResult<usize> {
+        let grid = grid.get();
+        Ok(py.detach(|| crate::day4::solve_part2(&grid.0))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part2, m)?)
+    }
+}
+
+mod day5 {
+    use super::*;
+    use crate::day5::{Rules, Updates};
+
+    #[pyfunction]
+    fn parse_input(input: &str) -> PyResult<(Rules, Updates)> {
+        Ok(crate::day5::parse_input(input)?)
+    }
+
+    #[pyfunction]
+    fn solve_part1(py: Python<'_>, rules: Rules, updates: Updates) -> PyResult<u32> {
+        Ok(py.detach(|| crate::day5::solve_part1(&rules, &updates))?)
+    }
+
+    #[pyfunction]
+    fn solve_part2(py: Python<'_>, rules: Rules, updates: Updates) -> PyResult<u32> {
+        Ok(py.detach(|| crate::day5::solve_part2(&rules, &updates))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part2, m)?)
+    }
+}
+
+mod day6 {
+    use super::*;
+
+    #[pyfunction]
+    fn parse_input(input: &str) -> PyResult<Grid> {
+        Ok(Grid(crate::day6::parse_input(input)?))
+    }
+
+    #[pyfunction]
+    fn solve_part1(py: Python<'_>, map: &Bound<'_, Grid>) -> PyResult<usize> {
+        let map = map.get();
+        Ok(py.detach(|| crate::day6::solve_part1(&map.0))?)
+    }
+
+    #[pyfunction]
+    fn solve_part2(py: Python<'_>, map: &Bound<'_, Grid>) -> PyResult<usize> {
+        let map = map.get();
+        Ok(py.detach(|| crate::day6::solve_part2(&map.0))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part2, m)?)
+    }
+}
+
+mod day7 {
+    use super::*;
+    use crate::day7::Equation;
+
+    // Equations cross as `(test_value, numbers)` tuples
+    fn equations(items: Vec<(i64, Vec<i64>)>) -> Vec<Equation> {
+        items.into_iter().map(|(test_value, numbers)| Equation { test_value, numbers }).collect()
+    }
+
+    #[pyfunction]
+    fn parse_input(input: &str) -> PyResult<Vec<(i64, Vec<i64>)>> {
+        let equations = crate::day7::parse_input(input)?;
+        Ok(equations.into_iter().map(|equation| (equation.test_value, equation.numbers)).collect())
+    }
+
+    /// Takes `part1_operators` as a keyword argument.
+    #[pyfunction]
+    #[pyo3(signature = (equations, **settings))]
+    fn solve_part1(py: Python<'_>, equations: Vec<(i64, Vec<i64>)>, settings: Option<&Bound<'_, PyDict>>) -> PyResult<i64> {
+        let config = config("day7", settings)?.day7;
+        let equations = self::equations(equations);
+        Ok(py.detach(|| crate::day7::solve_part1(&equations, &config))?)
+    }
+
+    /// Takes `part2_operators` as a keyword argument.
+    #[pyfunction]
+    #[pyo3(signature = (equations, **settings))]
+    fn solve_part2(py: Python<'_>, equations: Vec<(i64, Vec<i64>)>, settings: Option<&Bound<'_, PyDict>>) -> PyResult<i64> {
+        let config = config("day7", settings)?.day7;
+        let equations = self::equations(equations);
+        Ok(py.detach(|| crate::day7::solve_part2(&equations, &config))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part2, m)?)
+    }
+}
+
+mod day8 {
+    use super::*;
+
+    #[pyclass(frozen, module = "aoc_2024_rs.day8")]
+    pub struct AntennaMap(crate::day8::AntennaMap);
+
+    #[pymethods]
+    impl AntennaMap {
+        #[getter]
+        fn width(&self) -> i32 {
+            self.0.width()
+        }
+
+        #[getter]
+        fn height(&self) -> i32 {
+            self.0.height()
+        }
+
+        /// Antenna positions as `(x, y)`, keyed by frequency.
+        #[getter]
+        fn frequencies(&self) -> HashMap<char, Vec<(i32, i32)>> {
+            self.0
+                .frequencies()
+                .iter()
+                .map(|(&frequency, points)| (frequency, points.iter().copied().map(xy).collect()))
+                .collect()
+        }
+    }
+
+    #[pyfunction]
+    fn parse_input(input: &str) -> PyResult<AntennaMap> {
+        Ok(AntennaMap(crate::day8::parse_input(input)?))
+    }
+
+    #[pyfunction]
+    fn solve_part1(py: Python<'_>, map: &Bound<'_, AntennaMap>) -> PyResult<usize> {
+        let map = map.get();
+        Ok(py.detach(|| crate::day8::solve_part1(&map.0))?)
+    }
+
+    #[pyfunction]
+    fn solve_part2(py: Python<'_>, map: &Bound<'_, AntennaMap>) -> PyResult<usize> {
+        let map = map.get();
+        Ok(py.detach(|| crate::day8::solve_part2(&map.0))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_class::<AntennaMap>()?;
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part2, m)?)
+    }
+}
+
+mod day9 {
+    use super::*;
+
+    /// A disk laid out block by block from its disk map: the file id in each
+    /// block, or `None` where it is free.
+    #[pyclass(module = "aoc_2024_rs.day9")]
+    pub struct Disk {
+        blocks: Vec<Option<usize>>,
+    }
+
+    #[pymethods]
+    impl Disk {
+        #[new]
+        fn new(lengths: Vec<u32>) -> Self {
+            Disk { blocks: crate::day9::expand_disk_map(&lengths) }
+        }
+
+        #[getter]
+        fn blocks(&self) -> Vec<Option<usize>> {
+            self.blocks.clone()
+        }
+
+        /// Moves blocks one at a time into the leftmost free space, as in
+        /// part 1.
+        fn compact(&mut self, py: Python<'_>) -> PyResult<()> {
+            let blocks = &mut self.blocks;
+            Ok(py.detach(|| crate::day9::compact_disk(blocks))?)
+        }
+
+        /// Moves whole files into the leftmost space they fit, as in part 2.
+        fn compact_whole_files(&mut self, py: Python<'_>) -> PyResult<()> {
+            let blocks = &mut self.blocks;
+            Ok(py.detach(|| crate::day9::compact_disk_whole_files(blocks))?)
+        }
+
+        fn checksum(&self) -> usize {
+            crate::day9::calculate_checksum(&self.blocks)
+        }
+
+        fn __len__(&self) -> usize {
+            self.blocks.len()
+        }
+    }
+
+    #[pyfunction]
+    fn parse_input(input: &str) -> PyResult<Vec<u32>> {
+        Ok(crate::day9::parse_input(input)?)
+    }
+
+    #[pyfunction]
+    fn solve_part1(py: Python<'_>, lengths: Vec<u32>) -> PyResult<usize> {
+        Ok(py.detach(|| crate::day9::solve_part1(&lengths))?)
+    }
+
+    #[pyfunction]
+    fn solve_part2(py: Python<'_>, lengths: Vec<u32>) -> PyResult<usize> {
+        Ok(py.detach(|| crate::day9::solve_part2(&lengths))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_class::<Disk>()?;
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part2, m)?)
+    }
+}
+
+mod day10 {
+    use super::*;
+
+    #[pyclass(frozen, module = "aoc_2024_rs.day10")]
+    pub struct TopographicMap(crate::day10::TopographicMap);
+
+    #[pyfunction]
+    fn parse_input(input: &str) -> PyResult<TopographicMap> {
+        Ok(TopographicMap(crate::day10::parse_input(input)?))
+    }
+
+    #[pyfunction]
+    fn solve_part1(py: Python<'_>, map: &Bound<'_, TopographicMap>) -> PyResult<usize> {
+        let map = map.get();
+        Ok(py.detach(|| crate::day10::solve_part1(&map.0))?)
+    }
+
+    #[pyfunction]
+    fn solve_part2(py: Python<'_>, map: &Bound<'_, TopographicMap>) -> PyResult<usize> {
+        let map = map.get();
+        Ok(py.detach(|| crate::day10::solve_part2(&map.0))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_class::<TopographicMap>()?;
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part2, m)?)
+    }
+}
+
+mod day11 {
+    use super::*;
+
+    #[pyfunction]
+    fn parse_input(input: &str) -> PyResult<Vec<u64>> {
+        Ok(crate::day11::parse_input(input)?)
+    }
+
+    /// Takes `blinks` as a keyword argument.
+    #[pyfunction]
+    #[pyo3(signature = (stones, **settings))]
+    fn solve_part1(py: Python<'_>, stones: Vec<u64>, settings: Option<&Bound<'_, PyDict>>) -> PyResult<usize> {
+        let config = config("day11", settings)?.day11;
+        Ok(py.detach(|| crate::day11::solve_part1(&stones, &config))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)
+    }
+}
+
+mod day12 {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[pyclass(frozen, module = "aoc_2024_rs.day12")]
+    pub struct Field(crate::day12::Field);
+
+    #[pymethods]
+    impl Field {
+        /// The regions, in the order of the Rust `Field::regions`: by first plot,
+        /// row by row.
+        fn regions(slf: &Bound<'_, Self>) -> Vec<Region> {
+            (0..slf.get().0.regions().len())
+                .map(|index| Region { field: slf.clone().unbind(), index })
+                .collect()
+        }
+
+        fn price(&self) -> i32 {
+            self.0.price()
+        }
+
+        fn bulk_price(&self) -> i32 {
+            self.0.bulk_price()
+        }
+    }
+
+    /// One region of a [`Field`], which it keeps alive.
+    #[pyclass(frozen, module = "aoc_2024_rs.day12")]
+    pub struct Region {
+        field: Py<Field>,
+        index: usize,
+    }
+
+    impl Region {
+        fn region(&self) -> &crate::day12::Region {
+            &self.field.get().0.regions()[self.index]
+        }
+    }
+
+    #[pymethods]
+    impl Region {
+        #[getter]
+        fn plant(&self) -> char {
+            self.region().plant()
+        }
+
+        /// The plots in the region as `(x, y)`.
+        #[getter]
+        fn positions(&self) -> HashSet<(i32, i32)> {
+            self.region().positions().iter().copied().map(xy).collect()
+        }
+
+        #[getter]
+        fn area(&self) -> i32 {
+            self.region().area()
+        }
+
+        #[getter]
+        fn perimeter(&self) -> i32 {
+            self.region().perimeter()
+        }
+
+        #[getter]
+        fn sides(&self) -> i32 {
+            self.region().sides()
+        }
+
+        fn price(&self) -> i32 {
+            self.region().price()
+        }
+
+        fn side_price(&self) -> i32 {
+            self.region().side_price()
+        }
+
+        fn __repr__(&self) -> String {
+            format!("Region(plant={:?}, area={})", self.plant(), self.area())
+        }
+    }
+
+    #[pyfunction]
+    fn parse_input(input: &str) -> PyResult<Field> {
+        Ok(Field(crate::day12::parse_input(input)?))
+    }
+
+    #[pyfunction]
+    fn solve_part1(py: Python<'_>, field: &Bound<'_, Field>) -> PyResult<i32> {
+        let field = field.get();
+        Ok(py.detach(|| crate::day12::solve_part1(&field.0))?)
+    }
+
+    #[pyfunction]
+    fn solve_part2(py: Python<'_>, field: &Bound<'_, Field>) -> PyResult<i32> {
+        let field = field.get();
+        Ok(py.detach(|| crate::day12::solve_part2(&field.0))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_class::<Field>()?;
+        m.add_class::<Region>()?;
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part2, m)?)
+    }
+}
+
+mod day13 {
+    use super::*;
+
+    /// A claw machine, with each button's move and the prize as `(x, y)`.
+    #[pyclass(from_py_object, get_all, set_all, module = "aoc_2024_rs.day13")]
+    #[derive(Clone)]
+    pub struct Machine {
+        button_a: (i64, i64),
+        button_b: (i64, i64),
+        prize: (i64, i64),
+    }
+
+    #[pymethods]
+    impl Machine {
+        #[new]
+        fn new(button_a: (i64, i64), button_b: (i64, i64), prize: (i64, i64)) -> Self {
+            Machine { button_a, button_b, prize }
+        }
+
+        fn __repr__(&self) -> String {
+            format!("Machine(button_a={:?}, button_b={:?}, prize={:?})", self.button_a, self.button_b, self.prize)
+        }
+    }
+
+    impl From<&crate::day13::Machine> for Machine {
+        fn from(machine: &crate::day13::Machine) -> Self {
+            Machine::new(xy(machine.button_a), xy(machine.button_b), xy(machine.prize))
+        }
+    }
+
+    fn machines(items: Vec<Machine>) -> Vec<crate::day13::Machine> {
+        let point = |(x, y)| Point::new(x, y);
+        items
+            .into_iter()
+            .map(|machine| crate::day13::Machine {
+                button_a: point(machine.button_a),
+                button_b: point(machine.button_b),
+                prize: point(machine.prize),
+            })
+            .collect()
+    }
+
+    #[pyfunction]
+    fn parse_input(input: &str) -> PyResult<Vec<Machine>> {
+        Ok(crate::day13::parse_input(input)?.iter().map(Machine::from).collect())
+    }
+
+    /// Takes `a_cost` and `b_cost` as keyword arguments.
+    #[pyfunction]
+    #[pyo3(signature = (machines, **settings))]
+    fn solve_part1(py: Python<'_>, machines: Vec<Machine>, settings: Option<&Bound<'_, PyDict>>) -> PyResult<i64> {
+        let config = config("day13", settings)?.day13;
+        let machines = self::machines(machines);
+        Ok(py.detach(|| crate::day13::solve_part1(&machines, &config))?)
+    }
+
+    /// Takes `prize_offset`, `a_cost` and `b_cost` as keyword arguments.
+    #[pyfunction]
+    #[pyo3(signature = (machines, **settings))]
+    fn solve_part2(py: Python<'_>, machines: Vec<Machine>, settings: Option<&Bound<'_, PyDict>>) -> PyResult<i64> {
+        let config = config("day13", settings)?.day13;
+        let mut machines = self::machines(machines);
+        Ok(py.detach(|| crate::day13::solve_part2(&mut machines, &config))?)
+    }
+
+    pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_class::<Machine>()?;
+        m.add_function(wrap_pyfunction!(parse_input, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part1, m)?)?;
+        m.add_function(wrap_pyfunction!(solve_part2, m)?)
+    }
+}
+
+type Register = fn(&Bound<'_, PyModule>) -> PyResult<()>;
+
+/// Adds `aoc_2024_rs.<name>`, also under its full name in `sys.modules` so
+/// that `from aoc_2024_rs.day13 import Machine` works. maturin installs the
+/// extension as `aoc_2024_rs.aoc_2024_rs` inside a package of the same name,
+/// so the submodules are named after the top-level package.
+fn submodule(parent: &Bound<'_, PyModule>, name: &str, register: Register) -> PyResult<()> {
+    let py = parent.py();
+    let module = PyModule::new(py, name)?;
+    register(&module)?;
+    let parent_name = parent.name()?;
+    let package = parent_name.to_str()?.split('.').next().unwrap_or_default();
+    let qualified = format!("{}.{}", package, name);
+    module.setattr("__name__", &qualified)?;
+    parent.add_submodule(&module)?;
+    py.import("sys")?.getattr("modules")?.set_item(qualified, module)
+}
+
+/// The extension module itself.
+#[pymodule]
+pub fn aoc_2024_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("AocError", m.py().get_type::<AocError>())?;
+    m.add_class::<Grid>()?;
+    m.add_function(wrap_pyfunction!(days, m)?)?;
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+
+    let days: [(&str, Register); 13] = [
+        ("day1", day1::register),
+        ("day2", day2::register),
+        ("day3", day3::register),
+        ("day4", day4::register),
+        ("day5", day5::register),
+        ("day6", day6::register),
+        ("day7", day7::register),
+        ("day8", day8::register),
+        ("day9", day9::register),
+        ("day10", day10::register),
+        ("day11", day11::register),
+        ("day12", day12::register),
+        ("day13", day13::register),
+    ];
+    for (name, register) in days {
+        submodule(m, name, register)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::bench::format_duration;
+use crate::runner::{DayRun, Status};
+use crate::solution::Answer;
+
+/// A machine-readable format for `run --format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!("unknown format: {} (expected json, csv or markdown)", s)),
+        }
+    }
+}
+
+/// The result of one part of one day on one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: Option<Answer>,
+    pub duration: Duration,
+    pub error: Option<String>,
+}
+
+/// Flattens runs into one record per part. A part without an answer carries
+/// its day's error, or `timeout`.
+pub fn records(runs: &[DayRun], input: &str) -> Vec<Record> {
+    let mut records = Vec::new();
+    for run in runs {
+        let parts = [(1, &run.part1, run.part1_time), (2, &run.part2, run.part2_time)];
+        for (part, answer, duration) in parts {
+            let error = match (&run.status, answer) {
+                (_, Some(_)) | (Status::Ok, None) => None,
+                (Status::Error(message), None) => Some(message.clone()),
+                (Status::Timeout, None) => Some("timeout".to_string()),
+            };
+            records.push(Record {
+                day: run.day,
+                part,
+                input: input.to_string(),
+                answer: answer.clone(),
+                duration,
+                error,
+            });
+        }
+    }
+    records
+}
+
+/// Renders records in a machine-readable format. Durations are whole
+/// nanoseconds, except in Markdown, which is meant to be read.
+pub fn render(format: Format, records: &[Record]) -> String {
+    match format {
+        Format::Json => json(records),
+        Format::Csv => csv(records),
+        Format::Markdown => markdown(records),
+    }
+}
+
+fn json(records: &[Record]) -> String {
+    let mut out = String::from("[\n");
+    for (i, record) in records.iter().enumerate() {
+        let answer = json_answer(record.answer.as_ref());
+        let error = record.error.as_deref().map_or_else(|| "null".to_string(), json_string);
+        let _ = write!(
+            out,
+            "  {{\"day\": {}, \"part\": {}, \"input\": {}, \"answer\": {}, \"duration_ns\": {}, \"error\": {}}}",
+            record.day,
+            record.part,
+            json_string(&record.input),
+            answer,
+            record.duration.as_nanos(),
+            error
+        );
+        out.push_str(if i + 1 < records.len() { ",\n" } else { "\n" });
+    }
+    out.push_str("]\n");
+    out
+}
+
+/// An answer as a JSON value: numbers as numbers, text as a string, and
+/// `null` when there is none.
+pub(crate) fn json_answer(answer: Option<&Answer>) -> String {
+    match answer {
+        Some(Answer::Number(n)) => n.to_string(),
+        Some(Answer::Text(s)) => json_string(s),
+        Some(Answer::Unsolved) | None => "null".to_string(),
+    }
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv(records: &[Record]) -> String {
+    let mut out = String::from("day,part,input,answer,duration_ns,error\n");
+    for record in records {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            record.day,
+            record.part,
+            csv_field(&record.input),
+            csv_field(&answer_text(&record.answer)),
+            record.duration.as_nanos(),
+            csv_field(record.error.as_deref().unwrap_or(""))
+        );
+    }
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn markdown(records: &[Record]) -> String {
+    let mut out = String::from("| Day | Part | Input | Answer | Time | Error |\n|---:|---:|---|---:|---:|---|\n");
+    for record in records {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} |",
+            record.day,
+            record.part,
+            markdown_cell(&record.input),
+            markdown_cell(&answer_text(&record.answer)),
+            format_duration(record.duration),
+            markdown_cell(record.error.as_deref().unwrap_or(""))
+        );
+    }
+    out
+}
+
+fn markdown_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+fn answer_text(answer: &Option<Answer>) -> String {
+    match answer {
+        Some(Answer::Unsolved) | None => String::new(),
+        Some(answer) => answer.to_string(),
+    }
+}
@@ -0,0 +1,104 @@
+//! Answers kept on disk between runs, so days whose input and solver have
+//! not changed are not solved again.
+//!
+//! Each answer is keyed by day, part, a hash of the input and the solver's
+//! [`cache_version`](crate::solution::cache_version), and lives in the user's cache
+//! directory next to the downloaded inputs, as
+//! `<dir>/<year>/results/dayN/partP-<input hash>-<version hash>`.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::event;
+use crate::input::{self, YEAR};
+use crate::solution::Answer;
+
+/// A stable 64-bit FNV-1a hash. `std`'s hashers may change between Rust
+/// releases, which would quietly empty the cache.
+pub fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+#[derive(Debug, Clone)]
+pub struct ResultCache {
+    dir: PathBuf,
+}
+
+impl ResultCache {
+    /// A cache in `dir` itself, mostly for tests.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        ResultCache { dir: dir.into() }
+    }
+
+    /// The cache under the user's cache directory, the one
+    /// [`InputCache::from_env`](crate::input::InputCache::from_env) uses.
+    pub fn from_env() -> io::Result<Self> {
+        Ok(ResultCache::new(input::cache_dir()?.join(YEAR.to_string()).join("results")))
+    }
+
+    fn day_dir(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{}", day))
+    }
+
+    pub fn path(&self, day: u8, part: u8, input: &str, version: &str) -> PathBuf {
+        self.day_dir(day)
+            .join(format!("part{}-{:016x}-{:016x}", part, hash(input.as_bytes()), hash(version.as_bytes())))
+    }
+
+    /// The stored answer, if there is one. Anything unreadable counts as a
+    /// miss.
+    pub fn get(&self, day: u8, part: u8, input: &str, version: &str) -> Option<Answer> {
+        let text = fs::read_to_string(self.path(day, part, input, version)).ok()?;
+        let (kind, value) = text.split_once('\n')?;
+        let value = value.strip_suffix('\n')?;
+        let answer = match kind {
+            "number" => Answer::Number(value.parse().ok()?),
+            "text" => Answer::Text(value.to_string()),
+            _ => return None,
+        };
+        event!(Debug, "results", "cache hit", day = day, part = part);
+        Some(answer)
+    }
+
+    /// Stores an answer. `Unsolved` is never stored, so a stub that later
+    /// gets solved is not stuck answering `unsolved`.
+    pub fn put(&self, day: u8, part: u8, input: &str, version: &str, answer: &Answer) -> io::Result<()> {
+        let text = match answer {
+            Answer::Number(n) => format!("number\n{}\n", n),
+            Answer::Text(s) => format!("text\n{}\n", s),
+            Answer::Unsolved => return Ok(()),
+        };
+        let path = self.path(day, part, input, version);
+        fs::create_dir_all(self.day_dir(day))?;
+        // Written aside and renamed, as with cached inputs, so a reader never
+        // sees half an answer
+        let partial = path.with_extension("part");
+        fs::write(&partial, text)?;
+        fs::rename(&partial, &path)
+    }
+
+    /// Removes every stored answer, or only those for `day`, and returns how
+    /// many there were.
+    pub fn clear(&self, day: Option<u8>) -> io::Result<usize> {
+        let dirs = match day {
+            Some(day) => vec![self.day_dir(day)],
+            None => match fs::read_dir(&self.dir) {
+                Ok(entries) => entries.map(|entry| entry.map(|entry| entry.path())).collect::<io::Result<_>>()?,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+                Err(err) => return Err(err),
+            },
+        };
+
+        let mut removed = 0;
+        for dir in dirs {
+            match fs::read_dir(&dir) {
+                Ok(entries) => removed += entries.count(),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            }
+            fs::remove_dir_all(&dir)?;
+        }
+        Ok(removed)
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::bench::format_duration;
+use crate::cancel::Budget;
+use crate::error::AocError;
+use crate::event;
+use crate::results::ResultCache;
+use crate::solution::{cache_version, Answer, Solution};
+
+/// One day to run: its solver constructor and its input, or why the input
+/// could not be read, and where to look for answers from earlier runs.
+pub struct Job {
+    pub day: u8,
+    pub new: fn() -> Box<dyn Solution>,
+    pub input: std::result::Result<String, String>,
+    pub cache: Option<ResultCache>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Error(String),
+    Timeout,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Error(_) => write!(f, "error"),
+            Status::Timeout => write!(f, "timeout"),
+        }
+    }
+}
+
+/// The answers and timings for one day.
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub day: u8,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    pub parse_time: Duration,
+    pub part1_time: Duration,
+    pub part2_time: Duration,
+    /// Whether the answer came from the [`ResultCache`] rather than the
+    /// solver, in which case its time is zero.
+    pub part1_cached: bool,
+    pub part2_cached: bool,
+    pub status: Status,
+}
+
+impl DayRun {
+    fn new(day: u8) -> Self {
+        DayRun {
+            day,
+            part1: None,
+            part2: None,
+            parse_time: Duration::ZERO,
+            part1_time: Duration::ZERO,
+            part2_time: Duration::ZERO,
+            part1_cached: false,
+            part2_cached: false,
+            status: Status::Ok,
+        }
+    }
+
+    fn fail(&mut self, message: String) {
+        if self.status == Status::Ok {
+            self.status = Status::Error(message);
+        }
+    }
+
+    // A solver that ran out of its budget timed out rather than failed
+    fn fail_with(&mut self, err: AocError) {
+        match err {
+            AocError::Timeout { .. } if self.status == Status::Ok => self.status = Status::Timeout,
+            err => self.fail(err.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RunOptions {
+    /// Worker threads; each day also runs its two parts side by side.
+    pub jobs: usize,
+    /// How long a day may take, from parsing to both answers, before it is
+    /// reported as a timeout.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            jobs: thread::available_parallelism().map_or(1, |n| n.get()),
+            timeout: None,
+        }
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Parses and solves one day, running both parts concurrently under
+/// `budget`. A solver that notices the budget has run out ends the run with
+/// [`Status::Timeout`]. Parts with an answer in the job's cache are not
+/// solved again, and new answers are stored there.
+pub fn run_job(job: Job, budget: &Budget) -> DayRun {
+    let mut run = DayRun::new(job.day);
+    let input = match job.input {
+        Ok(input) => input,
+        Err(err) => {
+            run.fail(err);
+            return run;
+        }
+    };
+
+    let mut solution = (job.new)();
+    let version = cache_version(job.day, solution.as_ref());
+    let cached = [1, 2].map(|part| job.cache.as_ref().and_then(|cache| cache.get(job.day, part, &input, &version)));
+    run.part1_cached = cached[0].is_some();
+    run.part2_cached = cached[1].is_some();
+    if let [Some(part1), Some(part2)] = cached {
+        run.part1 = Some(part1);
+        run.part2 = Some(part2);
+        event!(Info, "runner", "day answered from the cache", day = run.day);
+        return run;
+    }
+
+    // A panicking solver fails its day rather than taking down the thread
+    // running it
+    let panicked = |what: &str| AocError::puzzle(job.day, format!("{} panicked", what));
+    let guarded = |f: &dyn Fn() -> (Result<Answer, AocError>, Duration), what: &str| {
+        panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| (Err(panicked(what)), Duration::ZERO))
+    };
+
+    let (parsed, parse_time) = timed(|| {
+        panic::catch_unwind(AssertUnwindSafe(|| budget.enter(|| solution.parse(&input))))
+            .unwrap_or_else(|_| Err(panicked("parsing")))
+    });
+    run.parse_time = parse_time;
+    if let Err(err) = parsed {
+        run.fail_with(err);
+        return run;
+    }
+
+    let solution = &*solution;
+    let solve = |part: u8| match &cached[part as usize - 1] {
+        Some(answer) => (Ok(answer.clone()), Duration::ZERO),
+        None if part == 1 => timed(|| budget.enter(|| solution.part1())),
+        None => timed(|| budget.enter(|| solution.part2())),
+    };
+    let ((part1, part1_time), (part2, part2_time)) = thread::scope(|scope| {
+        let part2 = scope.spawn(|| guarded(&|| solve(2), "part 2"));
+        let part1 = guarded(&|| solve(1), "part 1");
+        let part2 = part2
+            .join()
+            .unwrap_or_else(|_| (Err(panicked("part 2")), Duration::ZERO));
+        (part1, part2)
+    });
+
+    run.part1_time = part1_time;
+    run.part2_time = part2_time;
+    for (part, result) in [(1, part1), (2, part2)] {
+        match result {
+            Ok(answer) => {
+                if let (Some(cache), None) = (&job.cache, &cached[part as usize - 1]) {
+                    if let Err(err) = cache.put(job.day, part, &input, &version, &answer) {
+                        event!(Warn, "runner", "could not cache an answer", day = run.day, part = part, error = err);
+                    }
+                }
+                if part == 1 {
+                    run.part1 = Some(answer);
+                } else {
+                    run.part2 = Some(answer);
+                }
+            }
+            Err(err) => run.fail_with(err),
+        }
+    }
+    event!(
+        Info,
+        "runner",
+        "day finished",
+        day = run.day,
+        status = run.status,
+        parse = run.parse_time,
+        part1 = run.part1_time,
+        part2 = run.part2_time,
+    );
+    run
+}
+
+enum Event {
+    Started(usize, Instant, Budget),
+    Finished(usize, Box<DayRun>),
+}
+
+/// Runs every job on a pool of worker threads and returns the results in the
+/// order the jobs were given, however the threads were scheduled.
+///
+/// Each day runs under a [`Budget`] of `options.timeout`. A day that
+/// overruns it is reported as a timeout and its budget is cancelled, which
+/// stops solvers that call [`crate::cancel::check`]. Its worker is replaced
+/// too, so even a solver that never checks cannot stall the rest.
+pub fn run_parallel(jobs: Vec<Job>, options: RunOptions) -> Vec<DayRun> {
+    let days: Vec<u8> = jobs.iter().map(|job| job.day).collect();
+    let queue = Arc::new(Mutex::new(jobs.into_iter().enumerate().collect::<VecDeque<_>>()));
+    let (sender, receiver) = mpsc::channel();
+
+    let spawn_worker = |sender: &Sender<Event>| {
+        let queue = Arc::clone(&queue);
+        let sender = sender.clone();
+        thread::spawn(move || loop {
+            let next = queue.lock().ok().and_then(|mut queue| queue.pop_front());
+            let Some((index, job)) = next else {
+                break;
+            };
+            let budget = Budget::new(options.timeout);
+            let _ = sender.send(Event::Started(index, Instant::now(), budget.clone()));
+            let _ = sender.send(Event::Finished(index, Box::new(run_job(job, &budget))));
+        });
+    };
+    for _ in 0..options.jobs.clamp(1, days.len().max(1)) {
+        spawn_worker(&sender);
+    }
+    // Kept only while there are jobs left for a replacement worker to take,
+    // so that once every worker has exited the channel disconnects rather
+    // than leaving the loop below waiting forever
+    let mut sender = Some(sender);
+
+    let mut results: Vec<Option<DayRun>> = vec![None; days.len()];
+    let mut running: HashMap<usize, (Instant, Budget)> = HashMap::new();
+
+    while results.iter().any(Option::is_none) {
+        if queue.lock().map_or(true, |queue| queue.is_empty()) {
+            sender = None;
+        }
+        let now = Instant::now();
+        let next_deadline = options
+            .timeout
+            .and_then(|timeout| running.values().map(|&(start, _)| start + timeout).min());
+        let event = match next_deadline {
+            Some(deadline) => receiver.recv_timeout(deadline.saturating_duration_since(now)),
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match event {
+            Ok(Event::Started(index, start, budget)) => {
+                running.insert(index, (start, budget));
+            }
+            Ok(Event::Finished(index, run)) => {
+                running.remove(&index);
+                results[index].get_or_insert(*run);
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let timeout = options.timeout.unwrap_or_default();
+                let overdue: Vec<usize> = running
+                    .iter()
+                    .filter(|(_, (start, _))| start.elapsed() >= timeout)
+                    .map(|(&index, _)| index)
+                    .collect();
+                for index in overdue {
+                    if let Some((_, budget)) = running.remove(&index) {
+                        budget.cancel();
+                    }
+                    event!(Warn, "runner", "day timed out", day = days[index], timeout = timeout);
+                    let mut run = DayRun::new(days[index]);
+                    run.status = Status::Timeout;
+                    results[index] = Some(run);
+                    if let Some(sender) = &sender {
+                        spawn_worker(sender);
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    results
+        .into_iter()
+        .zip(days)
+        .map(|(run, day)| {
+            run.unwrap_or_else(|| {
+                let mut run = DayRun::new(day);
+                run.fail("no worker was left to run it".to_string());
+                run
+            })
+        })
+        .collect()
+}
+
+/// The runs as aligned text, one row per day, followed by any error
+/// messages.
+pub fn render(runs: &[DayRun]) -> String {
+    let answer = |a: &Option<Answer>| a.as_ref().map_or_else(|| "-".to_string(), Answer::to_string);
+    // A day that timed out has no times worth showing, and a cached part
+    // took none
+    let time = |run: &DayRun, d: Duration, cached: bool| match run.status {
+        Status::Timeout => "-".to_string(),
+        _ if cached => "cached".to_string(),
+        _ => format_duration(d),
+    };
+    let rows: Vec<[String; 7]> = runs
+        .iter()
+        .map(|run| {
+            [
+                run.day.to_string(),
+                answer(&run.part1),
+                answer(&run.part2),
+                time(run, run.parse_time, run.part1_cached && run.part2_cached),
+                time(run, run.part1_time, run.part1_cached),
+                time(run, run.part2_time, run.part2_cached),
+                run.status.to_string(),
+            ]
+        })
+        .collect();
+
+    let header = ["Day", "Part 1", "Part 2", "Parse", "Part 1 time", "Part 2 time", "Status"];
+    let mut widths = header.map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: [&str; 7]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, width))| match i {
+                // Answers and times line up on the right, like numbers
+                1..=5 => format!("{:>width$}", cell, width = width),
+                _ => format!("{:<width$}", cell, width = width),
+            })
+            .collect();
+        cells.join("  ").trim_end().to_string()
+    };
+
+    let mut text = line(header) + "\n";
+    for row in &rows {
+        text += &format!("{}\n", line(row.each_ref().map(String::as_str)));
+    }
+
+    for run in runs {
+        if let Status::Error(message) = &run.status {
+            text += &format!("\nday {}: {}\n", run.day, message);
+        }
+    }
+    text
+}
+
@@ -0,0 +1,195 @@
+//! `new <day>`: the files and registrations a new day needs, so adding one
+//! is a single command rather than edits across the tree.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The module written to `src/dayN.rs`. Its parts answer `Unsolved`, so the
+/// crate builds straight away and `run` works while the day is in progress.
+const TEMPLATE: &str = r#"use crate::error::Result;
+use crate::parser::Parser;
+use crate::solution::{Answer, Solution};
+
+const DAY: u8 = {day};
+
+#[derive(Default)]
+pub struct Day{day} {
+    lines: Vec<String>,
+}
+
+impl Solution for Day{day} {
+    fn parse(&mut self, input: &str) -> Result<()> {
+        self.lines = parse_input(input)?;
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        solve_part1(&self.lines)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        solve_part2(&self.lines)
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<String>> {
+    let p = Parser::new(DAY, input);
+    p.lines(input, |line| Ok(line.to_string()))
+}
+
+pub fn solve_part1(_lines: &[String]) -> Result<Answer> {
+    Ok(Answer::Unsolved)
+}
+
+pub fn solve_part2(_lines: &[String]) -> Result<Answer> {
+    Ok(Answer::Unsolved)
+}
+"#;
+
+/// Appended to `tests/examples.rs`. It fails until the example and its
+/// answers are filled in.
+const TEST_TEMPLATE: &str = r#"
+// Paste the example into fixtures/day{day}.txt and its answers into
+// fixtures/answers.toml under [example.day{day}]
+#[test]
+fn day{day}() {
+    let lines = day{day}::parse_input(&fixture({day})).unwrap();
+    assert_parts({day}, day{day}::solve_part1(&lines), day{day}::solve_part2(&lines));
+}
+"#;
+
+/// Creates everything day `day` needs under the crate root `root`:
+/// `src/dayN.rs`, its `mod` line and registry entry, an empty input and
+/// example fixture, and a test in `tests/examples.rs`. Returns the files it
+/// created or changed. Refuses to touch a day whose module already exists.
+pub fn scaffold(root: &Path, day: u8) -> io::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(invalid(format!("day {} is not between 1 and 25", day)));
+    }
+    let module = root.join("src").join(format!("day{}.rs", day));
+    if module.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", module.display()),
+        ));
+    }
+
+    // Work out every edit before writing anything, so a surprise in one file
+    // leaves the tree as it was
+    let lib = root.join("src").join("lib.rs");
+    let registry = root.join("src").join("solution.rs");
+    let tests = root.join("tests").join("examples.rs");
+    let lib_text = add_mod(&read(&lib)?, day).ok_or_else(|| invalid(format!("{}: no `pub mod dayN;` lines", lib.display())))?;
+    let registry_text = register(&read(&registry)?, day)
+        .ok_or_else(|| invalid(format!("{}: cannot find the day imports and `DAYS`", registry.display())))?;
+    let tests_text = read(&tests)? + &fill(TEST_TEMPLATE, day);
+
+    fs::write(&module, fill(TEMPLATE, day))?;
+    fs::write(&lib, lib_text)?;
+    fs::write(&registry, registry_text)?;
+    fs::write(&tests, tests_text)?;
+    let mut changed = vec![module, lib, registry, tests];
+
+    for dir in ["inputs", "fixtures"] {
+        let path = root.join(dir).join(format!("day{}.txt", day));
+        if !path.exists() {
+            fs::create_dir_all(root.join(dir))?;
+            fs::write(&path, "")?;
+            changed.push(path);
+        }
+    }
+
+    Ok(changed)
+}
+
+fn fill(template: &str, day: u8) -> String {
+    template.replace("{day}", &day.to_string())
+}
+
+fn read(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path).map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// The number in `pub mod dayN;` or `dayN::DayN`-style names
+fn day_number(name: &str) -> Option<u8> {
+    name.strip_prefix("day")?.parse().ok()
+}
+
+/// Adds `pub mod dayN;` to `lib.rs`, keeping the day modules in numeric order.
+fn add_mod(lib: &str, day: u8) -> Option<String> {
+    let lines: Vec<&str> = lib.lines().collect();
+    let modules: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, day_number(line.strip_prefix("pub mod ")?.strip_suffix(';')?)?)))
+        .collect();
+    let (first, _) = *modules.first()?;
+    let at = modules.iter().rev().find(|&&(_, n)| n < day).map_or(first, |&(i, _)| i + 1);
+
+    let declaration = format!("pub mod day{};", day);
+    Some(insert_line(&lines, at, &declaration))
+}
+
+/// Adds the day to the `use crate::{...}` imports and to `DAYS` in
+/// `solution.rs`.
+fn register(registry: &str, day: u8) -> Option<String> {
+    let start = registry
+        .match_indices("use crate::{")
+        .map(|(i, _)| i)
+        .find(|&i| registry[i + "use crate::{".len()..].trim_start().starts_with("day"))?;
+    let end = start + registry[start..].find("};")? + 2;
+    let mut names: Vec<String> = registry[start + "use crate::{".len()..end - 2]
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    names.push(format!("day{}", day));
+    // The order rustfmt keeps imports in
+    names.sort();
+    let registry = format!("{}{}{}", &registry[..start], imports(&names), &registry[end..]);
+
+    let lines: Vec<&str> = registry.lines().collect();
+    let table = lines.iter().position(|line| line.starts_with("pub const DAYS"))?;
+    let close = table + lines[table..].iter().position(|line| line.trim() == "];")?;
+    let at = (table + 1..close)
+        .find(|&i| {
+            let entry = lines[i].trim().strip_prefix("Day { day: ");
+            entry.and_then(|rest| rest.split(',').next()?.parse::<u8>().ok()).is_some_and(|n| n > day)
+        })
+        .unwrap_or(close);
+
+    let entry = format!("    Day {{ day: {day}, new: boxed::<day{day}::Day{day}>, source: include_str!(\"day{day}.rs\") }},");
+    Some(insert_line(&lines, at, &entry))
+}
+
+// `use crate::{...};` on one line if it fits in 100 columns, otherwise one
+// indented, wrapped list the way rustfmt lays it out
+fn imports(names: &[String]) -> String {
+    let single = format!("use crate::{{{}}};", names.join(", "));
+    if single.len() <= 100 {
+        return single;
+    }
+
+    let mut text = String::from("use crate::{\n");
+    let mut line = String::new();
+    for name in names {
+        if !line.is_empty() && 4 + line.len() + 1 + name.len() + 1 > 100 {
+            text += &format!("    {}\n", line.trim_end());
+            line.clear();
+        }
+        line += &format!("{}, ", name);
+    }
+    text += &format!("    {}\n}};", line.trim_end());
+    text
+}
+
+fn insert_line(lines: &[&str], at: usize, line: &str) -> String {
+    let mut out: Vec<&str> = lines.to_vec();
+    out.insert(at, line);
+    out.join("\n") + "\n"
+}
@@ -0,0 +1,299 @@
+//! Graph searches over an implicit graph: nodes are any hashable values, and
+//! the edges out of a node come from a neighbour function the caller passes in.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The nodes a search reached, in the order it reached them, with the cost of
+/// getting to each and the node it came from.
+#[derive(Debug, Clone)]
+pub struct Reached<N, C = usize> {
+    order: Vec<N>,
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Reached<N, C> {
+    fn new() -> Self {
+        Reached {
+            order: Vec::new(),
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+        }
+    }
+
+    /// Records `node` unless it was already reached; returns whether it was new.
+    fn reach(&mut self, node: N, cost: C, parent: Option<&N>) -> bool {
+        match self.costs.entry(node.clone()) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(cost);
+                if let Some(parent) = parent {
+                    self.parents.insert(node.clone(), parent.clone());
+                }
+                self.order.push(node);
+                true
+            }
+        }
+    }
+
+    pub fn order(&self) -> &[N] {
+        &self.order
+    }
+
+    pub fn into_order(self) -> Vec<N> {
+        self.order
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.costs.contains_key(node)
+    }
+
+    /// The cost of reaching `node`: steps for BFS and DFS, total edge cost for
+    /// Dijkstra.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// The route from a start node to `node`, both ends included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.contains(node).then(|| reconstruct_path(&self.parents, node.clone()))
+    }
+}
+
+/// Walks `parents` back from `end` to a node without a parent, and returns the
+/// route in forward order.
+pub fn reconstruct_path<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, end: N) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(parent) = parents.get(path.last().expect("path is never empty")) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Breadth-first search from every node in `starts`. Nodes come out in
+/// order of their distance in steps.
+pub fn bfs<N, I>(starts: impl IntoIterator<Item = N>, mut neighbours: impl FnMut(&N) -> I) -> Reached<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = Reached::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if reached.reach(start.clone(), 0, None) {
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let steps = reached.cost(&node).unwrap_or_default() + 1;
+        for next in neighbours(&node) {
+            if reached.reach(next.clone(), steps, Some(&node)) {
+                queue.push_back(next);
+            }
+        }
+    }
+    reached
+}
+
+/// Depth-first search from `start`, in preorder. Costs are depths in the
+/// search tree, not shortest distances.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Reached<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = Reached::new();
+    let mut stack = vec![(start, 0, None)];
+
+    while let Some((node, depth, parent)) = stack.pop() {
+        if !reached.reach(node.clone(), depth, parent.as_ref()) {
+            continue;
+        }
+        // Pushed in reverse so the first neighbour is explored first
+        let next: Vec<N> = neighbours(&node).into_iter().filter(|n| !reached.contains(n)).collect();
+        for n in next.into_iter().rev() {
+            stack.push((n, depth + 1, Some(node.clone())));
+        }
+    }
+    reached
+}
+
+/// Every node connected to `start` through `neighbours`, `start` first.
+pub fn flood_fill<N, I>(start: N, neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs([start], neighbours).into_order()
+}
+
+/// Shortest paths by total edge cost from `start` to every reachable node.
+/// `neighbours` yields `(node, edge cost)` pairs; costs must not be negative.
+pub fn dijkstra<N, C, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Reached<N, C>
+where
+    N: Clone + Eq + Hash + Ord,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut reached = Reached::new();
+    let mut best: HashMap<N, (C, Option<N>)> = HashMap::from([(start.clone(), (C::default(), None))]);
+    let mut heap = BinaryHeap::from([Reverse((C::default(), start))]);
+
+    while let Some(Reverse((cost, node))) = heap.pop() {
+        if reached.contains(&node) {
+            continue;
+        }
+        let parent = best.get(&node).and_then(|(_, parent)| parent.clone());
+        reached.reach(node.clone(), cost, parent.as_ref());
+
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            let improves = best.get(&next).is_none_or(|&(known, _)| next_cost < known);
+            if !reached.contains(&next) && improves {
+                best.insert(next.clone(), (next_cost, Some(node.clone())));
+                heap.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    reached
+}
+
+/// The cheapest path from `start` to a node satisfying `is_goal`, with its
+/// cost. `heuristic` must never overestimate the remaining cost; with a
+/// heuristic of zero this is Dijkstra stopping at the first goal.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash + Ord,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs: HashMap<N, C> = HashMap::from([(start.clone(), C::default())]);
+    let mut parents: HashMap<N, N> = HashMap::new();
+    let mut closed = HashSet::new();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), start))]);
+
+    while let Some(Reverse((_, cost, node))) = heap.pop() {
+        if is_goal(&node) {
+            return Some((reconstruct_path(&parents, node), cost));
+        }
+        if !closed.insert(node.clone()) {
+            continue;
+        }
+
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|&known| next_cost < known) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(Reverse((next_cost + heuristic(&next), next_cost, next)));
+            }
+        }
+    }
+    None
+}
+
+/// How many distinct paths lead from `start` to nodes satisfying `is_goal`.
+/// The graph must be acyclic; counts are memoised per node.
+pub fn count_paths<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> usize
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    fn count<N: Clone + Eq + Hash, I: IntoIterator<Item = N>>(
+        node: &N,
+        neighbours: &mut impl FnMut(&N) -> I,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        memo: &mut HashMap<N, usize>,
+    ) -> usize {
+        if let Some(&paths) = memo.get(node) {
+            return paths;
+        }
+        let paths = if is_goal(node) {
+            1
+        } else {
+            let next: Vec<N> = neighbours(node).into_iter().collect();
+            next.iter().map(|n| count(n, neighbours, is_goal, memo)).sum()
+        };
+        memo.insert(node.clone(), paths);
+        paths
+    }
+
+    count(&start, &mut neighbours, &mut is_goal, &mut HashMap::new())
+}
+
+/// The nodes that could not be ordered because they sit on, or behind, a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N> {
+    pub nodes: Vec<N>,
+}
+
+/// Orders `nodes` so every edge `(before, after)` points forwards, using
+/// Kahn's algorithm. Ties keep the order `nodes` were given in, and repeated
+/// nodes count once. Edges that mention a node not in `nodes` are ignored.
+pub fn topological_sort<N>(
+    nodes: impl IntoIterator<Item = N>,
+    edges: impl IntoIterator<Item = (N, N)>,
+) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Clone + Eq + Hash,
+{
+    let mut seen = HashSet::new();
+    let nodes: Vec<N> = nodes.into_iter().filter(|n| seen.insert(n.clone())).collect();
+    let mut in_degree: HashMap<N, usize> = nodes.iter().map(|n| (n.clone(), 0)).collect();
+    let mut successors: HashMap<N, Vec<N>> = HashMap::new();
+
+    for (before, after) in edges {
+        if in_degree.contains_key(&before) {
+            if let Some(count) = in_degree.get_mut(&after) {
+                *count += 1;
+                successors.entry(before).or_default().push(after);
+            }
+        }
+    }
+
+    let mut queue: VecDeque<N> = nodes.iter().filter(|n| in_degree[*n] == 0).cloned().collect();
+    let mut sorted = Vec::with_capacity(nodes.len());
+
+    while let Some(node) = queue.pop_front() {
+        for next in successors.get(&node).into_iter().flatten() {
+            if let Some(count) = in_degree.get_mut(next) {
+                *count -= 1;
+                if *count == 0 {
+                    queue.push_back(next.clone());
+                }
+            }
+        }
+        sorted.push(node);
+    }
+
+    if sorted.len() == nodes.len() {
+        Ok(sorted)
+    } else {
+        Err(Cycle {
+            nodes: nodes.into_iter().filter(|n| in_degree[n] > 0).collect(),
+        })
+    }
+}
@@ -0,0 +1,249 @@
+//! `serve`: the solvers over HTTP on localhost, for notebooks and other
+//! tools that would rather not shell out.
+//!
+//! `POST /day/{n}/part/{p}` takes the puzzle input as its body and answers
+//! with one JSON object:
+//!
+//! ```text
+//! {"day": 1, "part": 1, "answer": 11, "parse_ns": 5120, "solve_ns": 840, "cached": false}
+//! ```
+//!
+//! Anything that goes wrong comes back with a 4xx or 5xx status and
+//! `{"error": "..."}`. `GET /days` lists the registered days.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::cancel::Budget;
+use crate::error::AocError;
+use crate::event;
+use crate::input::check_input;
+use crate::report::{json_answer, json_string};
+use crate::results::ResultCache;
+use crate::solution::{cache_version, DAYS};
+
+pub const DEFAULT_PORT: u16 = 2024;
+
+// Real inputs are tens of kilobytes; generated ones can be far bigger
+const MAX_BODY: usize = 64 << 20;
+// How long a client may take to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Default)]
+pub struct ServeOptions {
+    /// How long one request may spend parsing and solving.
+    pub timeout: Option<Duration>,
+    pub cache: Option<ResultCache>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+/// A response, whose body is always JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Response { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl AsRef<str>) -> Self {
+        Response {
+            status,
+            body: format!("{{\"error\": {}}}", json_string(message.as_ref())),
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Content Too Large",
+        422 => "Unprocessable Content",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+/// Answers requests on `listener` until it fails, one thread per
+/// connection and one request per connection.
+pub fn serve(listener: TcpListener, options: ServeOptions) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let options = options.clone();
+        thread::spawn(move || {
+            if let Err(err) = connection(stream, &options) {
+                event!(Debug, "serve", "connection failed", error = err);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn connection(stream: TcpStream, options: &ServeOptions) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+
+    let start = Instant::now();
+    let (line, response) = match read_request(&mut reader, &mut writer) {
+        Ok(request) => (format!("{} {}", request.method, request.path), handle(&request, options)),
+        Err(response) => ("-".to_string(), response),
+    };
+    event!(Info, "serve", "answered", request = line, status = response.status, time = start.elapsed());
+
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        response.body.len(),
+        response.body
+    )?;
+    writer.flush()
+}
+
+/// Reads one HTTP/1.1 request. Bodies need a `Content-Length`; clients that
+/// ask with `Expect: 100-continue`, as curl does for large bodies, are told
+/// to go ahead on `writer`.
+fn read_request(reader: &mut impl BufRead, writer: &mut impl Write) -> Result<Request, Response> {
+    let bad = |message: &str| Response::error(400, message);
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|_| bad("could not read the request"))?;
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(path), Some(_version)) = (words.next(), words.next(), words.next()) else {
+        return Err(bad("malformed request line"));
+    };
+
+    let (mut length, mut chunked, mut expect_continue) = (None, false, false);
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).map_err(|_| bad("could not read the headers"))? == 0 || header.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else { continue };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            length = Some(value.parse::<usize>().map_err(|_| bad("bad Content-Length"))?);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            chunked = true;
+        } else if name.eq_ignore_ascii_case("expect") {
+            expect_continue = value.eq_ignore_ascii_case("100-continue");
+        }
+    }
+
+    if chunked {
+        return Err(Response::error(411, "send the input with a Content-Length"));
+    }
+    let length = length.unwrap_or(0);
+    if length > MAX_BODY {
+        return Err(Response::error(413, format!("the body is over {} bytes", MAX_BODY)));
+    }
+    if expect_continue && length > 0 {
+        let _ = writer.write_all(b"HTTP/1.1 100 Continue\r\n\r\n");
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(|_| bad("the body is shorter than its Content-Length"))?;
+
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        body,
+    })
+}
+
+/// Routes a request. Separate from the socket handling, so it can be
+/// called directly.
+pub fn handle(request: &Request, options: &ServeOptions) -> Response {
+    // Query strings are not used, but should not break routing
+    let path = request.path.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["days"]) => {
+            let days: Vec<String> = DAYS.iter().map(|entry| entry.day.to_string()).collect();
+            Response::ok(format!("{{\"days\": [{}]}}", days.join(", ")))
+        }
+        ("POST", ["day", day, "part", part]) => {
+            let (Ok(day), Ok(part)) = (day.parse::<u8>(), part.parse::<u8>()) else {
+                return Response::error(404, format!("no such puzzle: {}", path));
+            };
+            solve(day, part, &request.body, options)
+        }
+        (_, ["days"] | ["day", _, "part", _]) => Response::error(405, format!("{} is not allowed here", request.method)),
+        _ => Response::error(404, format!("no such endpoint: {}", path)),
+    }
+}
+
+fn solve(day: u8, part: u8, body: &[u8], options: &ServeOptions) -> Response {
+    let Some(entry) = DAYS.iter().find(|entry| entry.day == day) else {
+        return Response::error(404, format!("no solution registered for day {}", day));
+    };
+    if !(1..=2).contains(&part) {
+        return Response::error(404, format!("day {} has no part {}", day, part));
+    }
+    let Ok(input) = std::str::from_utf8(body) else {
+        return Response::error(400, "the input is not UTF-8");
+    };
+    if let Err(problem) = check_input(input) {
+        return Response::error(400, problem);
+    }
+
+    let answered = |answer, parse_time: Duration, solve_time: Duration, cached: bool| {
+        Response::ok(format!(
+            "{{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}, \"cached\": {}}}",
+            day,
+            part,
+            json_answer(Some(answer)),
+            parse_time.as_nanos(),
+            solve_time.as_nanos(),
+            cached
+        ))
+    };
+
+    let mut solution = (entry.new)();
+    let version = cache_version(day, solution.as_ref());
+    if let Some(answer) = options.cache.as_ref().and_then(|cache| cache.get(day, part, input, &version)) {
+        return answered(&answer, Duration::ZERO, Duration::ZERO, true);
+    }
+
+    let budget = Budget::new(options.timeout);
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+        let start = Instant::now();
+        budget.enter(|| solution.parse(input))?;
+        let parse_time = start.elapsed();
+        let start = Instant::now();
+        let answer = budget.enter(|| if part == 1 { solution.part1() } else { solution.part2() })?;
+        Ok((answer, parse_time, start.elapsed()))
+    }));
+
+    match solved {
+        Ok(Ok((answer, parse_time, solve_time))) => {
+            if let Some(cache) = &options.cache {
+                if let Err(err) = cache.put(day, part, input, &version, &answer) {
+                    event!(Warn, "serve", "could not cache an answer", day = day, part = part, error = err);
+                }
+            }
+            answered(&answer, parse_time, solve_time, false)
+        }
+        Ok(Err(err @ AocError::Timeout { .. })) => Response::error(503, err.to_string()),
+        Ok(Err(err)) => Response::error(422, err.to_string()),
+        Err(_) => Response::error(500, format!("day {} part {} panicked", day, part)),
+    }
+}
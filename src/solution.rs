@@ -0,0 +1,133 @@
+use std::fmt;
+
+use crate::config::{self, Config};
+use crate::error::Result;
+use crate::results;
+use crate::{day1, day10, day11, day12, day13, day2, day3, day4, day5, day6, day7, day8, day9};
+
+/// The answer to one part of a puzzle. Numbers are wide enough to hold any
+/// `i64`, `u64` or `usize` a solver returns without loss.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(i128::from(n))
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, i64, u32, u64);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        // usize is at most 64 bits on every supported target
+        Answer::Number(n as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+/// A day's puzzle: parse the input once, then answer both parts from it.
+/// Solvers are shared between threads, so `run all` can answer both parts
+/// side by side.
+pub trait Solution: Send + Sync {
+    fn parse(&mut self, input: &str) -> Result<()>;
+    fn part1(&self) -> Result<Answer>;
+    fn part2(&self) -> Result<Answer>;
+
+    /// What the result cache files this solver's answers under, alongside a
+    /// hash of its source (see [`cache_version`]). Solvers with a config
+    /// include it.
+    fn version(&self) -> String {
+        "1".to_string()
+    }
+}
+
+/// A solver with puzzle constants that `aoc.toml` can change, built from
+/// its day's section of the config.
+pub trait Configurable: Solution + Sized {
+    fn with_config(config: &Config) -> Self;
+}
+
+/// An entry in the day registry.
+pub struct Day {
+    pub day: u8,
+    pub new: fn() -> Box<dyn Solution>,
+    /// The solver's module source, so that editing it retires the answers
+    /// the result cache holds for the day.
+    pub source: &'static str,
+}
+
+/// The modules solvers build on, whose changes can change any day's answers.
+const SHARED_SOURCES: &[&str] = &[
+    include_str!("geom.rs"),
+    include_str!("grid.rs"),
+    include_str!("math.rs"),
+    include_str!("parser.rs"),
+    include_str!("search.rs"),
+];
+
+fn boxed<S: Solution + Default + 'static>() -> Box<dyn Solution> {
+    Box::new(S::default())
+}
+
+fn configured<S: Configurable + 'static>() -> Box<dyn Solution> {
+    Box::new(S::with_config(&config::current()))
+}
+
+pub const DAYS: &[Day] = &[
+    Day { day: 1, new: boxed::<day1::Day1>, source: include_str!("day1.rs") },
+    Day { day: 2, new: configured::<day2::Day2>, source: include_str!("day2.rs") },
+    Day { day: 3, new: boxed::<day3::Day3>, source: include_str!("day3.rs") },
+    Day { day: 4, new: boxed::<day4::Day4>, source: include_str!("day4.rs") },
+    Day { day: 5, new: boxed::<day5::Day5>, source: include_str!("day5.rs") },
+    Day { day: 6, new: boxed::<day6::Day6>, source: include_str!("day6.rs") },
+    Day { day: 7, new: configured::<day7::Day7>, source: include_str!("day7.rs") },
+    Day { day: 8, new: boxed::<day8::Day8>, source: include_str!("day8.rs") },
+    Day { day: 9, new: boxed::<day9::Day9>, source: include_str!("day9.rs") },
+    Day { day: 10, new: boxed::<day10::Day10>, source: include_str!("day10.rs") },
+    Day { day: 11, new: configured::<day11::Day11>, source: include_str!("day11.rs") },
+    Day { day: 12, new: boxed::<day12::Day12>, source: include_str!("day12.rs") },
+    Day { day: 13, new: configured::<day13::Day13>, source: include_str!("day13.rs") },
+];
+
+/// Looks up a day in the registry and creates a fresh solver for it.
+pub fn solution(day: u8) -> Option<Box<dyn Solution>> {
+    DAYS.iter().find(|d| d.day == day).map(|d| (d.new)())
+}
+
+/// What the result cache files `solution`'s answers for `day` under: its
+/// [`Solution::version`] and a hash of the day's source and the modules it
+/// builds on, so a changed solver is never answered from the cache.
+pub fn cache_version(day: u8, solution: &dyn Solution) -> String {
+    let source = DAYS.iter().find(|d| d.day == day).map_or("", |d| d.source);
+    let mut bytes = source.as_bytes().to_vec();
+    for shared in SHARED_SOURCES {
+        bytes.extend_from_slice(shared.as_bytes());
+    }
+    format!("{} {:016x}", solution.version(), results::hash(&bytes))
+}
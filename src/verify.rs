@@ -0,0 +1,132 @@
+use std::fmt;
+use std::fs::read_to_string;
+use std::io;
+use std::path::Path;
+
+use toml::{Table, Value};
+
+use crate::error::AocError;
+use crate::solution::{Answer, Solution};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Known-good answers, keyed by input name, then day, then part:
+///
+/// ```toml
+/// [default.day7]
+/// part1 = 3749
+/// part2 = 11387
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    table: Table,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = read_to_string(path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+        Self::parse(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), err)))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        Ok(Answers {
+            table: text.parse()?,
+        })
+    }
+
+    /// The expected answer, as it would be printed.
+    pub fn expected(&self, input: &str, day: u8, part: u8) -> Option<String> {
+        let value = self
+            .table
+            .get(input)?
+            .get(format!("day{}", day))?
+            .get(format!("part{}", part))?;
+        match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Integer(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// No expected answer is recorded for this input.
+    Unknown,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail => write!(f, "FAIL"),
+            Status::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// The outcome of checking one part of one day against the answers file.
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub actual: Result<Answer, AocError>,
+    pub expected: Option<String>,
+}
+
+impl Check {
+    pub fn status(&self) -> Status {
+        match (&self.actual, &self.expected) {
+            (Err(_), _) => Status::Fail,
+            (Ok(_), None) => Status::Unknown,
+            (Ok(actual), Some(expected)) if actual.to_string() == *expected => Status::Pass,
+            (Ok(_), Some(_)) => Status::Fail,
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day {:>2} part {} [{}] {:<7}", self.day, self.part, self.input, self.status())?;
+        match (&self.actual, &self.expected) {
+            (Err(err), _) => write!(f, " {}", err),
+            (Ok(actual), Some(expected)) if self.status() == Status::Fail => {
+                write!(f, " expected {}, got {}", expected, actual)
+            }
+            (Ok(actual), _) => write!(f, " {}", actual),
+        }
+    }
+}
+
+/// Solves both parts of a day and compares them with the recorded answers.
+pub fn verify_day(
+    day: u8,
+    mut solution: Box<dyn Solution>,
+    input_name: &str,
+    input: &str,
+    answers: &Answers,
+) -> Vec<Check> {
+    let parsed = solution.parse(input);
+
+    [1, 2]
+        .into_iter()
+        .map(|part| {
+            let actual = match (&parsed, part) {
+                (Err(err), _) => Err(err.clone()),
+                (Ok(()), 1) => solution.part1(),
+                (Ok(()), _) => solution.part2(),
+            };
+            Check {
+                day,
+                part,
+                input: input_name.to_string(),
+                actual,
+                expected: answers.expected(input_name, day, part),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,160 @@
+//! `run <day> --watch`: reruns a day whenever its input file changes, and
+//! shows how the answers moved.
+
+use std::fs;
+use std::ops::ControlFlow;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::bench::format_duration;
+use crate::cancel::Budget;
+use crate::input::InputSource;
+use crate::runner::{self, DayRun, Job};
+use crate::solution::{Answer, Solution};
+
+/// Notices changes to a file by polling its modification time and size.
+pub struct FileWatcher {
+    path: PathBuf,
+    // `None` until the first poll; the inner `None` means the file is missing
+    stamp: Option<Option<(SystemTime, u64)>>,
+}
+
+impl FileWatcher {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileWatcher {
+            path: path.into(),
+            stamp: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the file changed since the last poll, including appearing or
+    /// disappearing. The first poll always counts as a change.
+    pub fn poll(&mut self) -> bool {
+        let stamp = fs::metadata(&self.path)
+            .ok()
+            .map(|meta| (meta.modified().unwrap_or(SystemTime::UNIX_EPOCH), meta.len()));
+        self.stamp.replace(stamp) != Some(stamp)
+    }
+}
+
+/// Polls `watcher` every `interval` and reruns `day` from `source` after
+/// each change, once the file has stopped changing, giving each run
+/// `timeout`. `report` sees every run with the one before it, and stops the
+/// watch by returning `Break`.
+pub fn watch(
+    day: u8,
+    new: fn() -> Box<dyn Solution>,
+    source: &InputSource,
+    mut watcher: FileWatcher,
+    interval: Duration,
+    timeout: Option<Duration>,
+    mut report: impl FnMut(&DayRun, Option<&DayRun>) -> ControlFlow<()>,
+) {
+    let mut previous: Option<DayRun> = None;
+    loop {
+        if watcher.poll() {
+            // Editors often truncate and then write, so wait for it to settle
+            while {
+                thread::sleep(interval);
+                watcher.poll()
+            } {}
+
+            // No result cache: the point is to see the solver run again
+            let job = |input| Job { day, new, input, cache: None };
+            let input = source.read(day).map_err(|err| err.to_string());
+            let budget = Budget::new(timeout);
+            let run = panic::catch_unwind(AssertUnwindSafe(|| runner::run_job(job(input), &budget)))
+                .unwrap_or_else(|_| runner::run_job(job(Err("the solver panicked".to_string())), &budget));
+            if report(&run, previous.as_ref()).is_break() {
+                return;
+            }
+            previous = Some(run);
+        }
+        thread::sleep(interval);
+    }
+}
+
+/// One part's answer in two consecutive runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub part: u8,
+    pub old: String,
+    pub new: String,
+}
+
+fn answer(answer: &Option<Answer>) -> String {
+    answer.as_ref().map_or_else(|| "-".to_string(), Answer::to_string)
+}
+
+/// The parts whose answers differ between `old` and `new`. A part without
+/// an answer, because of an error, shows as `-`.
+pub fn changes(old: &DayRun, new: &DayRun) -> Vec<Change> {
+    [(1, &old.part1, &new.part1), (2, &old.part2, &new.part2)]
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(part, old, new)| Change {
+            part,
+            old: answer(old),
+            new: answer(new),
+        })
+        .collect()
+}
+
+/// A run as text. When any answer changed since `previous`, the old and new
+/// answers of both parts are laid out side by side.
+pub fn render(run: &DayRun, previous: Option<&DayRun>) -> String {
+    let mut text = format!("day {}: {}", run.day, run.status);
+    // A run whose input could not be read never got to parse it
+    if run.parse_time > Duration::ZERO {
+        text += &format!(", parsed in {}", format_duration(run.parse_time));
+    }
+    text.push('\n');
+    let parts = [(1, &run.part1, run.part1_time), (2, &run.part2, run.part2_time)];
+    let changed = previous.map(|previous| changes(previous, run)).unwrap_or_default();
+
+    let rows: Vec<[String; 5]> = match previous {
+        Some(previous) if !changed.is_empty() => {
+            let mut rows = vec![["".into(), "old".into(), "new".into(), "".into(), "".into()]];
+            for ((part, new, time), old) in parts.into_iter().zip([&previous.part1, &previous.part2]) {
+                let mark = if changed.iter().any(|change| change.part == part) { "changed" } else { "" };
+                rows.push([format!("Part {}", part), answer(old), answer(new), format_duration(time), mark.into()]);
+            }
+            rows
+        }
+        _ => parts
+            .into_iter()
+            .map(|(part, new, time)| [format!("Part {}", part), answer(new), format_duration(time), "".into(), "".into()])
+            .collect(),
+    };
+
+    let mut widths = [0; 5];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    for row in &rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, width))| match i {
+                0 | 4 => format!("{:<width$}", cell, width = width),
+                _ => format!("{:>width$}", cell, width = width),
+            })
+            .collect();
+        text += cells.join("  ").trim_end();
+        text.push('\n');
+    }
+
+    if let runner::Status::Error(message) = &run.status {
+        text += &format!("{}\n", message);
+    }
+    text
+}
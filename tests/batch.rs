@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_2024_rs::batch::{self, Outcome};
+use aoc_2024_rs::runner::RunOptions;
+use aoc_2024_rs::solution::DAYS;
+use aoc_2024_rs::verify::Answers;
+
+const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc_batch_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write(path: &Path, text: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, text).unwrap();
+}
+
+#[test]
+fn files_and_profile_directories_are_inputs() {
+    let dir = scratch_dir("inputs");
+    write(&dir.join("carol.txt"), EXAMPLE);
+    write(&dir.join("alice").join("day1.txt"), EXAMPLE);
+    write(&dir.join("bob").join("day2.txt"), EXAMPLE);
+    write(&dir.join(".notes"), "not an input");
+
+    let inputs = batch::inputs(&dir, 1).unwrap();
+    let names: Vec<&str> = inputs.iter().map(|input| input.name.as_str()).collect();
+    assert_eq!(names, ["alice", "carol"]);
+    assert_eq!(inputs[0].path, dir.join("alice").join("day1.txt"));
+
+    assert!(batch::inputs(&dir.join("missing"), 1).is_err());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn every_input_is_checked_against_its_answers() {
+    let dir = scratch_dir("matrix");
+    write(&dir.join("alice.txt"), EXAMPLE);
+    write(&dir.join("bob.txt"), EXAMPLE);
+    write(&dir.join("carol.txt"), "3   4\n4   x\n");
+    write(&dir.join("dave.txt"), EXAMPLE);
+    let answers = Answers::parse("[alice.day1]\npart1 = 11\npart2 = 31\n\n[bob.day1]\npart1 = 11\npart2 = 30\n").unwrap();
+
+    let inputs = batch::inputs(&dir, 1).unwrap();
+    let rows = batch::run(1, DAYS[0].new, &inputs, &answers, RunOptions::default(), None);
+    let outcomes: Vec<(&str, Outcome)> = rows.iter().map(|row| (row.input.as_str(), row.outcome())).collect();
+    assert_eq!(
+        outcomes,
+        [("alice", Outcome::Pass), ("bob", Outcome::Mismatch), ("carol", Outcome::Error), ("dave", Outcome::Unknown)]
+    );
+    assert_eq!((rows[1].part_outcome(1), rows[1].part_outcome(2)), (Outcome::Pass, Outcome::Mismatch));
+
+    let text = batch::render(&rows, false);
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines[0].starts_with("Input") && lines[0].ends_with("Status"), "{}", text);
+    assert!(lines[2].starts_with("bob") && lines[2].ends_with("MISMATCH"), "{}", text);
+    assert!(lines[3].starts_with("carol") && lines[3].contains(" - "), "{}", text);
+    assert!(text.contains("bob: part 2 expected 30, got 31"), "{}", text);
+    assert!(text.contains("carol: day 1, line 2"), "{}", text);
+    assert!(!text.contains('\x1b'));
+
+    // Only the problem rows are coloured
+    let colored = batch::render(&rows, true);
+    let red: Vec<&str> = colored.lines().filter(|line| line.starts_with('\x1b')).collect();
+    assert_eq!(red.len(), 2, "{}", colored);
+    assert!(red[0].contains("bob") && red[1].contains("carol"));
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,49 @@
+use std::thread;
+use std::time::Duration;
+
+use aoc_2024_rs::cancel::{self, Budget};
+use aoc_2024_rs::AocError;
+
+#[test]
+fn checks_pass_outside_any_budget() {
+    for _ in 0..5000 {
+        cancel::check(1).unwrap();
+    }
+}
+
+#[test]
+fn cancelling_reaches_every_thread_in_the_budget() {
+    let budget = Budget::unlimited();
+    budget.enter(|| cancel::check(6)).unwrap();
+
+    let other = budget.clone();
+    thread::spawn(move || other.cancel()).join().unwrap();
+    assert!(budget.is_cancelled());
+    assert_eq!(budget.enter(|| cancel::check(6)), Err(AocError::Timeout { day: 6 }));
+
+    let on_another_thread = budget.clone();
+    let result = thread::spawn(move || on_another_thread.enter(|| cancel::check(7))).join().unwrap();
+    assert_eq!(result, Err(AocError::Timeout { day: 7 }));
+}
+
+#[test]
+fn the_deadline_is_noticed_without_cancelling() {
+    let budget = Budget::new(Some(Duration::from_millis(10)));
+    thread::sleep(Duration::from_millis(20));
+    let calls = budget.enter(|| (1..=10_000).take_while(|_| cancel::check(9).is_ok()).count());
+    assert!(calls < 2048, "{} calls", calls);
+    assert!(budget.is_cancelled());
+}
+
+#[test]
+fn entering_restores_the_outer_budget() {
+    let outer = Budget::unlimited();
+    outer.cancel();
+    let inner = Budget::unlimited();
+
+    outer.enter(|| {
+        assert!(inner.enter(|| cancel::check(1)).is_ok());
+        assert!(cancel::check(1).is_err());
+    });
+    assert!(cancel::check(1).is_ok());
+}
@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use aoc_2024_rs::config::{self, Config};
+use aoc_2024_rs::solution;
+use aoc_2024_rs::{day11, day13, day2, day7};
+
+#[test]
+fn an_empty_file_keeps_the_puzzle_constants() {
+    let config = Config::parse("").unwrap();
+    assert_eq!(config, Config::default());
+    assert_eq!(config.day11.blinks, 75);
+    assert_eq!((config.day2.min_step, config.day2.max_step), (1, 3));
+    assert_eq!(config.day7.part2_operators, ['+', '*', '|']);
+    assert_eq!((config.day13.prize_offset, config.day13.a_cost, config.day13.b_cost), (10_000_000_000_000, 3, 1));
+
+    let missing = Path::new(env!("CARGO_MANIFEST_DIR")).join("no_such_config.toml");
+    assert_eq!(Config::load(&missing, false).unwrap(), Config::default());
+    assert!(Config::load(&missing, true).is_err());
+}
+
+#[test]
+fn sections_override_only_the_keys_they_set() {
+    let config = Config::parse("[day11]\nblinks = 25\n\n[day7]\npart2_operators = [\"+\", \"|\"]\n").unwrap();
+    assert_eq!(config.day11.blinks, 25);
+    assert_eq!(config.day7.part1_operators, ['+', '*']);
+    assert_eq!(config.day7.part2_operators, ['+', '|']);
+    assert_eq!(config.day13, day13::Config::default());
+}
+
+#[test]
+fn mistakes_are_reported_with_their_section_and_key() {
+    let error = |text: &str| Config::parse(text).unwrap_err();
+    assert_eq!(error("[day12]\nx = 1\n"), "unknown section [day12]");
+    assert_eq!(error("[day11]\nblink = 25\n"), "[day11] blink: unknown key");
+    assert_eq!(error("[day11]\nblinks = \"25\"\n"), "[day11] blinks: expected an integer");
+    assert_eq!(error("[day11]\nblinks = -1\n"), "[day11] blinks: -1 is out of range");
+    assert_eq!(error("[day11]\nblinks = 100000\n"), "[day11] blinks: at most 100 blinks are supported");
+    assert!(error("[day2]\nmin_step = 0\n").starts_with("[day2] min_step:"));
+    assert!(error("[day7]\npart1_operators = [\"-\"]\n").contains("unknown operator `-`"));
+    assert!(error("[day7]\npart1_operators = [\"+*\"]\n").contains("single characters"));
+    assert!(error("[day13]\nb_cost = -1\n").starts_with("[day13] b_cost:"));
+    assert!(error("day11 = 3\n").contains("must be a [day11] section"));
+}
+
+#[test]
+fn solvers_use_their_config() {
+    let stones = [125, 17];
+    assert_eq!(day11::solve_part1(&stones, &day11::Config { blinks: 6 }).unwrap(), 22);
+    assert_eq!(day11::solve_part1(&stones, &day11::Config { blinks: 25 }).unwrap(), 55312);
+    assert!(day11::solve_part1(&stones, &day11::Config { blinks: 100_000 }).is_err());
+
+    let wide = day2::Config { min_step: 1, max_step: 4 };
+    assert!(!day2::is_safe(&[1, 5, 6], &day2::Config::default()));
+    assert!(day2::is_safe(&[1, 5, 6], &wide));
+
+    let equations = day7::parse_input("156: 15 6\n").unwrap();
+    let concat_only = day7::Config { part1_operators: vec!['|'], part2_operators: vec!['+'] };
+    assert_eq!(day7::solve_part1(&equations, &concat_only).unwrap(), 156);
+    assert_eq!(day7::solve_part2(&equations, &concat_only).unwrap(), 0);
+
+    let mut machines = day13::parse_input("Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n").unwrap();
+    let flat = day13::Config { prize_offset: 0, a_cost: 1, b_cost: 1 };
+    assert_eq!(day13::solve_part1(&machines, &flat).unwrap(), 120);
+    assert_eq!(day13::solve_part2(&mut machines, &flat).unwrap(), 120);
+}
+
+#[test]
+fn the_registry_builds_solvers_with_the_startup_config() {
+    // The only test touching the process-wide config, so nothing races it
+    config::set(Config::parse("[day11]\nblinks = 25\n").unwrap());
+    let mut solver = solution::solution(11).unwrap();
+    solver.parse("125 17\n").unwrap();
+    assert_eq!(solver.part1().unwrap().to_string(), "55312");
+
+    config::set(Config::default());
+}
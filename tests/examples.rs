@@ -0,0 +1,195 @@
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use aoc_2024_rs::solution::DAYS;
+use aoc_2024_rs::verify::{self, Answers, Status};
+use aoc_2024_rs::*;
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+}
+
+fn fixture(day: u8) -> String {
+    let path = fixtures_dir().join(format!("day{}.txt", day));
+    read_to_string(&path).unwrap_or_else(|err| panic!("{}: {}", path.display(), err))
+}
+
+fn expected(day: u8, part: u8) -> String {
+    let answers = Answers::load(&fixtures_dir().join("answers.toml")).unwrap();
+    answers
+        .expected("example", day, part)
+        .unwrap_or_else(|| panic!("no example answer for day {} part {}", day, part))
+}
+
+fn assert_parts<T: ToString, U: ToString>(day: u8, part1: Result<T>, part2: Result<U>) {
+    assert_eq!(part1.unwrap().to_string(), expected(day, 1), "day {} part 1", day);
+    assert_eq!(part2.unwrap().to_string(), expected(day, 2), "day {} part 2", day);
+}
+
+#[test]
+fn every_registered_day_matches_its_examples() {
+    let answers = Answers::load(&fixtures_dir().join("answers.toml")).unwrap();
+
+    for entry in DAYS {
+        let checks = verify::verify_day(entry.day, (entry.new)(), "example", &fixture(entry.day), &answers);
+        for check in checks {
+            assert_ne!(check.status(), Status::Fail, "{}", check);
+        }
+    }
+}
+
+#[test]
+fn day1() {
+    let (left, right) = day1::parse_input(&fixture(1)).unwrap();
+    assert_eq!(left, [3, 4, 2, 1, 3, 3]);
+    assert_eq!(right, [4, 3, 5, 3, 9, 3]);
+    assert_parts(1, day1::solve_part1(&left, &right), day1::solve_part2(&left, &right));
+}
+
+#[test]
+fn day2() {
+    let reports = day2::parse_input(&fixture(2)).unwrap();
+    assert_eq!(reports.len(), 6);
+    assert_eq!(reports[0], [7, 6, 4, 2, 1]);
+    let config = day2::Config::default();
+    assert_parts(2, day2::solve_part1(&reports, &config), day2::solve_part2(&reports, &config));
+}
+
+#[test]
+fn day3() {
+    let instructions = day3::parse_input(&fixture(3)).unwrap();
+    assert_eq!(instructions.len(), 6);
+    assert_parts(3, day3::solve_part1(&instructions), day3::solve_part2(&instructions));
+}
+
+#[test]
+fn day4() {
+    let grid = day4::parse_input(&fixture(4)).unwrap();
+    assert_eq!((grid.width(), grid.height()), (10, 10));
+    assert_parts(4, day4::solve_part1(&grid), day4::solve_part2(&grid));
+}
+
+#[test]
+fn day5() {
+    let (rules, updates) = day5::parse_input(&fixture(5)).unwrap();
+    assert_eq!(rules.len(), 21);
+    assert_eq!(rules[0], (47, 53));
+    assert_eq!(updates.len(), 6);
+    assert_eq!(day5::topological_sort(&updates[3], &rules).unwrap(), [97, 75, 47, 61, 53]);
+    assert_parts(5, day5::solve_part1(&rules, &updates), day5::solve_part2(&rules, &updates));
+}
+
+#[test]
+fn day6() {
+    let map = day6::parse_input(&fixture(6)).unwrap();
+    assert_eq!(map.find(&'^'), Some((4, 6)));
+    assert_parts(6, day6::solve_part1(&map), day6::solve_part2(&map));
+}
+
+#[test]
+fn day7() {
+    let equations = day7::parse_input(&fixture(7)).unwrap();
+    assert_eq!(equations.len(), 9);
+    assert_eq!(equations[1].test_value, 3267);
+    assert_eq!(equations[1].numbers, [81, 40, 27]);
+    let config = day7::Config::default();
+    assert_parts(7, day7::solve_part1(&equations, &config), day7::solve_part2(&equations, &config));
+}
+
+#[test]
+fn day7_sum_overflow_is_an_error() {
+    let equations = day7::parse_input("9223372036854775807: 9223372036854775807\n1: 1\n").unwrap();
+    let config = day7::Config::default();
+    assert_eq!(
+        day7::solve_part1(&equations, &config),
+        Err(AocError::puzzle(7, "sum overflows i64"))
+    );
+}
+
+#[test]
+fn day8() {
+    let map = day8::parse_input(&fixture(8)).unwrap();
+    assert_eq!((map.width(), map.height()), (12, 12));
+    assert_eq!(map.frequencies()[&'0'].len(), 4);
+    assert_eq!(map.frequencies()[&'A'].len(), 3);
+    assert_parts(8, day8::solve_part1(&map), day8::solve_part2(&map));
+}
+
+#[test]
+fn day9() {
+    let lengths = day9::parse_input(&fixture(9)).unwrap();
+    assert_eq!(lengths.len(), 19);
+    assert_eq!(day9::expand_disk_map(&[1, 2, 3]), [Some(0), None, None, Some(1), Some(1), Some(1)]);
+    assert_parts(9, day9::solve_part1(&lengths), day9::solve_part2(&lengths));
+}
+
+#[test]
+fn day10() {
+    let grid = day10::parse_input(&fixture(10)).unwrap();
+    assert_parts(10, day10::solve_part1(&grid), day10::solve_part2(&grid));
+}
+
+#[test]
+fn day11() {
+    let stones = day11::parse_input(&fixture(11)).unwrap();
+    assert_eq!(stones, [125, 17]);
+    assert_eq!(day11::solve_part1(&stones, &day11::Config::default()).unwrap().to_string(), expected(11, 1));
+}
+
+#[test]
+fn day12() {
+    let field = day12::parse_input(&fixture(12)).unwrap();
+    assert_eq!(field.regions().len(), 11);
+    // In reading order of each region's first plot, the same on every run
+    let plants: String = field.regions().iter().map(|region| region.plant()).collect();
+    assert_eq!(plants, "RICFVJCEIMS");
+    assert_parts(12, day12::solve_part1(&field), day12::solve_part2(&field));
+}
+
+#[test]
+fn day13() {
+    let mut machines = day13::parse_input(&fixture(13)).unwrap();
+    assert_eq!(machines.len(), 4);
+    assert_eq!((machines[0].button_a.x, machines[0].button_a.y), (94, 34));
+    assert_eq!((machines[0].prize.x, machines[0].prize.y), (8400, 5400));
+    let config = day13::Config::default();
+    let part1 = day13::solve_part1(&machines, &config);
+    assert_parts(13, part1, day13::solve_part2(&mut machines, &config));
+
+    // Buttons along the same line: five presses of B beat two of A and one of B
+    let parallel = day13::parse_input("Button A: X+2, Y+2\nButton B: X+1, Y+1\nPrize: X=5, Y=5\n").unwrap();
+    assert_eq!(day13::solve_part1(&parallel, &config).unwrap(), 5);
+
+    // Buttons that only move along y are solved on that axis
+    let vertical = day13::parse_input("Button A: X+0, Y+3\nButton B: X+0, Y+1\nPrize: X=0, Y=7\n").unwrap();
+    assert_eq!(day13::solve_part1(&vertical, &config).unwrap(), 7);
+
+    // A button that does not move is left alone: three presses of B, and no
+    // way at all to the second prize
+    let still = day13::parse_input(
+        "Button A: X+0, Y+0\nButton B: X+2, Y+3\nPrize: X=6, Y=9\n\nButton A: X+0, Y+0\nButton B: X+2, Y+3\nPrize: X=5, Y=9\n",
+    )
+    .unwrap();
+    assert_eq!(day13::solve_part1(&still, &config).unwrap(), 3);
+}
+
+#[test]
+fn parse_errors_point_at_the_offending_text() {
+    let err = day1::parse_input("3   4\n4   x3\n").unwrap_err();
+    assert_eq!(
+        err,
+        AocError::Parse {
+            day: 1,
+            line: 2,
+            column: 5,
+            text: "x3".to_string(),
+            message: "invalid number".to_string(),
+        }
+    );
+
+    let err = day13::parse_input("Button A: X+94, Y+34\nButton B: X+22, Y=67\n").unwrap_err();
+    assert!(matches!(err, AocError::Parse { day: 13, line: 2, column: 16, .. }), "{}", err);
+
+    let err = day6::parse_input("..#\n...\n").and_then(|map| day6::solve_part1(&map)).unwrap_err();
+    assert!(matches!(err, AocError::Puzzle { day: 6, .. }), "{}", err);
+}
@@ -0,0 +1,145 @@
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use aoc_2024_rs::fetch::Fetcher;
+use aoc_2024_rs::input::{check_download, check_input, InputCache, InputSource};
+
+/// A stand-in puzzle server on loopback that answers each request with the
+/// next of `responses`, and reports every request line and cookie it got.
+fn serve(responses: Vec<String>) -> (String, Receiver<(String, String)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, requests) = mpsc::channel();
+
+    thread::spawn(move || {
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let (mut request, mut cookie) = (String::new(), String::new());
+            reader.read_line(&mut request).unwrap();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some(value) = line.strip_prefix("Cookie: ") {
+                    cookie = value.trim().to_string();
+                }
+            }
+            sender.send((request.trim().to_string(), cookie)).unwrap();
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (base_url, requests)
+}
+
+fn ok(body: &str) -> String {
+    format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc_fetch_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn missing_inputs_are_fetched_once_and_cached_by_year_and_day() {
+    let (base_url, requests) = serve(vec![ok("125 17\n")]);
+    let dir = scratch_dir("cached");
+    let cache = InputCache::new(&dir, Some(Fetcher::new(&base_url, "secret")));
+
+    assert_eq!(cache.get(2024, 11).unwrap(), "125 17\n");
+    assert_eq!(requests.recv().unwrap(), ("GET /2024/day/11/input HTTP/1.1".to_string(), "session=secret".to_string()));
+    assert_eq!(cache.path(2024, 11), dir.join("2024").join("day11.txt"));
+
+    // The server only answers once, so this must come from disk
+    assert_eq!(cache.get(2024, 11).unwrap(), "125 17\n");
+    assert_eq!(InputCache::new(&dir, None).get(2024, 11).unwrap(), "125 17\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn chunked_responses_are_reassembled() {
+    let body = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n2333\r\n7\r\n133121\n\r\n0\r\n\r\n";
+    let (base_url, _requests) = serve(vec![body.to_string()]);
+    assert_eq!(Fetcher::new(base_url, "secret").fetch(2024, 9).unwrap(), "2333133121\n");
+}
+
+#[test]
+fn bad_and_truncated_downloads_are_refused_and_not_cached() {
+    let short = "HTTP/1.1 200 OK\r\nContent-Length: 20\r\n\r\n125 17\n".to_string();
+    let (base_url, _requests) = serve(vec![
+        "HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n".to_string(),
+        short,
+        ok(""),
+        ok("125 17"),
+    ]);
+    let dir = scratch_dir("refused");
+    let cache = InputCache::new(&dir, Some(Fetcher::new(&base_url, "expired")));
+
+    let err = cache.get(2024, 11).unwrap_err();
+    assert!(err.to_string().contains("400 Bad Request"), "{}", err);
+    assert!(!err.to_string().contains("expired"), "the token leaked: {}", err);
+    for _ in 0..3 {
+        assert_eq!(cache.get(2024, 11).unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+    assert!(!cache.path(2024, 11).exists());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn without_a_session_a_missing_input_is_an_error() {
+    let dir = scratch_dir("offline");
+    let err = InputCache::new(&dir, None).get(2024, 9).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    assert!(err.to_string().contains("AOC_SESSION"), "{}", err);
+}
+
+#[test]
+fn empty_files_fail_the_check_and_truncated_downloads_too() {
+    assert!(check_input("").is_err());
+    assert!(check_input(" \n\n").is_err());
+    assert!(check_input("12\0").is_err());
+    assert!(check_download("").is_err());
+    assert!(check_download("....\n.#..\n..").is_err());
+
+    // A short last record is only suspicious in a download
+    assert!(check_input("....\n.#..\n..").is_ok());
+    assert!(check_input("125 17").is_ok());
+    for input in ["125 17", "....\n.#..\n....", "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n", "190: 10 19\n3267: 81 40 27\n83: 17 5\n"] {
+        assert!(check_download(input).is_ok(), "{}", input);
+    }
+}
+
+#[test]
+fn an_empty_default_input_falls_back_to_the_cache() {
+    let dir = scratch_dir("fallback");
+    let inputs = dir.join("inputs");
+    let cache = InputCache::new(dir.join("cache"), None);
+    fs::create_dir_all(dir.join("cache/2024")).unwrap();
+    fs::write(cache.path(2024, 14), "cached input\n").unwrap();
+
+    // Missing, then empty as `new` leaves it
+    for _ in 0..2 {
+        assert_eq!(InputSource::Default.resolved_path_in(&inputs, &cache, 14), Some(cache.path(2024, 14)));
+        assert_eq!(InputSource::Default.read_in(&inputs, &cache, 14).unwrap(), "cached input\n");
+        fs::create_dir_all(&inputs).unwrap();
+        fs::write(inputs.join("day14.txt"), "").unwrap();
+    }
+
+    fs::write(inputs.join("day14.txt"), "pasted input\n").unwrap();
+    assert_eq!(InputSource::Default.resolved_path_in(&inputs, &cache, 14), Some(inputs.join("day14.txt")));
+    assert_eq!(InputSource::Default.read_in(&inputs, &cache, 14).unwrap(), "pasted input\n");
+
+    // Other sources never fall back
+    let profile = InputSource::Profile("alice".to_string());
+    assert_eq!(profile.resolved_path_in(&inputs, &cache, 14), Some(inputs.join("alice/day14.txt")));
+    assert!(profile.read_in(&inputs, &cache, 14).is_err());
+    fs::remove_dir_all(&dir).unwrap();
+}
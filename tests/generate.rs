@@ -0,0 +1,38 @@
+use aoc_2024_rs::day7;
+use aoc_2024_rs::generate::{self, Rng};
+use aoc_2024_rs::solution::DAYS;
+
+#[test]
+fn generated_inputs_parse_and_solve() {
+    for entry in DAYS {
+        for seed in 0..5 {
+            let input = generate::generate(entry.day, 20, &mut Rng::new(seed)).unwrap();
+            let mut solution = (entry.new)();
+            solution
+                .parse(&input)
+                .unwrap_or_else(|err| panic!("seed {}: {}\n{}", seed, err, input));
+            solution.part1().unwrap_or_else(|err| panic!("seed {}: {}", seed, err));
+            solution.part2().unwrap_or_else(|err| panic!("seed {}: {}", seed, err));
+        }
+    }
+}
+
+#[test]
+fn same_seed_same_input() {
+    for entry in DAYS {
+        let first = generate::generate(entry.day, 10, &mut Rng::new(42));
+        let second = generate::generate(entry.day, 10, &mut Rng::new(42));
+        assert_eq!(first, second, "day {}", entry.day);
+    }
+}
+
+#[test]
+fn large_day7_inputs_stay_solvable() {
+    // Big enough that uncapped test values used to overflow the sum
+    let input = generate::generate(7, 8000, &mut Rng::new(1)).unwrap();
+    let equations = day7::parse_input(&input).unwrap();
+    let total = equations.iter().try_fold(0i64, |total, eq| total.checked_add(eq.test_value));
+    assert!(total.is_some(), "the test values overflow i64");
+    // Part 2 tries three operators and takes too long in a debug build
+    day7::solve_part1(&equations, &day7::Config::default()).unwrap();
+}
@@ -0,0 +1,34 @@
+use aoc_2024_rs::geom::{Direction, Point, Vec2};
+
+#[test]
+fn point_arithmetic() {
+    let a = Point::new(3, 4);
+    let b = Point::new(1, -2);
+    assert_eq!(a + b, Point::new(4, 2));
+    assert_eq!(a - b, Point::new(2, 6));
+    assert_eq!(a * 2, Point::new(6, 8));
+    assert_eq!(-a, Point::new(-3, -4));
+    assert_eq!(a.cross(a * 3), 0);
+    assert_eq!(Point::from((5i64, 6i64)), Point::<i64>::new(5, 6));
+
+    let mut c = a;
+    c += b;
+    c -= Vec2::new(0, 1);
+    assert_eq!(c, Point::new(4, 1));
+    assert_eq!(Point::new(0, 0) + Direction::North, Point::new(0, -1));
+}
+
+#[test]
+fn directions_turn_and_reverse() {
+    assert_eq!(Direction::North.turn_right(), Direction::East);
+    assert_eq!(Direction::North.turn_left(), Direction::West);
+    assert_eq!(Direction::West.turn_right(), Direction::North);
+    assert_eq!(Direction::NorthEast.reverse(), Direction::SouthWest);
+
+    for direction in Direction::ALL {
+        assert_eq!(direction.turn_right().turn_left(), direction);
+        assert_eq!(direction.offset() + direction.reverse().offset(), Point::new(0, 0));
+    }
+    let total = Direction::ORTHOGONAL.iter().fold(Point::new(0, 0), |p, &d| p + d);
+    assert_eq!(total, Point::new(0, 0));
+}
@@ -0,0 +1,64 @@
+use aoc_2024_rs::geom::{Direction, Point};
+use aoc_2024_rs::grid::Grid;
+use aoc_2024_rs::AocError;
+
+fn sample() -> Grid<char> {
+    Grid::parse(0, "abc\ndef\n", Some).unwrap()
+}
+
+#[test]
+fn parse_checks_shape_and_cells() {
+    let grid = sample();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(2, 1)], 'f');
+
+    let err = Grid::parse(0, "abc\nde\n", Some).unwrap_err();
+    assert!(matches!(err, AocError::Parse { line: 2, column: 1, .. }), "{}", err);
+
+    let err = Grid::parse(0, "12\n3x\n", |c| c.to_digit(10)).unwrap_err();
+    assert!(matches!(err, AocError::Parse { line: 2, column: 2, .. }), "{}", err);
+
+    assert!(Grid::parse(0, "\n", Some).is_err());
+}
+
+#[test]
+fn get_is_checked_on_every_edge() {
+    let grid = sample();
+    assert_eq!(grid.get(Point::new(0, 0)), Some(&'a'));
+    assert_eq!(grid.get(Point::new(-1, 0)), None);
+    assert_eq!(grid.get(Point::new(0, -1)), None);
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(grid.get(Point::new(0, 2)), None);
+    assert!(grid.contains(Point::new(2, 1)));
+}
+
+#[test]
+fn neighbourhoods_stay_in_bounds() {
+    let grid = sample();
+    let mut corner: Vec<_> = grid.neighbours4((0, 0)).collect();
+    corner.sort();
+    assert_eq!(corner, [(0, 1), (1, 0)]);
+    assert_eq!(grid.neighbours8((1, 0)).count(), 5);
+    assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+}
+
+#[test]
+fn lines_through_the_grid() {
+    let grid = sample();
+    assert_eq!(grid.row(1), ['d', 'e', 'f']);
+    assert_eq!(grid.rows().count(), 2);
+    assert_eq!(grid.column(1).collect::<String>(), "be");
+    assert_eq!(grid.diagonal(0, 0).collect::<String>(), "ae");
+    assert_eq!(grid.anti_diagonal(2, 0).collect::<String>(), "ce");
+    assert_eq!(grid.ray(Point::new(2, 1), Direction::West.offset()).collect::<String>(), "fed");
+}
+
+#[test]
+fn find_and_map() {
+    let grid = sample();
+    assert_eq!(grid.find(&'e'), Some((1, 1)));
+    assert_eq!(grid.find(&'z'), None);
+
+    let upper = grid.map(|c| c.to_ascii_uppercase());
+    assert_eq!(upper.find_all(&'D').collect::<Vec<_>>(), [(0, 1)]);
+}
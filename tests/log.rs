@@ -0,0 +1,67 @@
+use aoc_2024_rs::event;
+use aoc_2024_rs::log::{self, format_event, Filter, Level};
+
+#[test]
+fn verbosity_flags_map_to_levels() {
+    assert_eq!(Filter::verbosity(0).level_for("day6"), None);
+    assert_eq!(Filter::verbosity(1).level_for("day6"), Some(Level::Info));
+    assert_eq!(Filter::verbosity(2).level_for("runner"), Some(Level::Debug));
+    assert_eq!(Filter::verbosity(5).level_for("day6"), Some(Level::Trace));
+}
+
+#[test]
+fn directives_set_a_default_and_per_target_levels() {
+    let filter = Filter::parse("info, day6=trace,runner=off,day6=debug").unwrap();
+    assert_eq!(filter.level_for("day12"), Some(Level::Info));
+    assert_eq!(filter.level_for("day6"), Some(Level::Debug));
+    assert_eq!(filter.level_for("runner"), None);
+
+    assert!(filter.enabled(Level::Warn, "day12"));
+    assert!(!filter.enabled(Level::Debug, "day12"));
+    assert!(filter.enabled(Level::Debug, "day6"));
+    assert!(!filter.enabled(Level::Error, "runner"));
+
+    assert_eq!(Filter::parse("").unwrap(), Filter::default());
+    assert!(Filter::parse("loud").is_err());
+    assert!(Filter::parse("day6=loud").is_err());
+}
+
+#[test]
+fn env_directives_refine_the_verbosity_flag() {
+    let filter = Filter::verbosity(1).with(Filter::parse("day11=trace").unwrap());
+    assert_eq!(filter.level_for("day11"), Some(Level::Trace));
+    assert_eq!(filter.level_for("day6"), Some(Level::Info));
+
+    let filter = Filter::verbosity(3).with(Filter::parse("warn").unwrap());
+    assert_eq!(filter.level_for("day6"), Some(Level::Warn));
+}
+
+#[test]
+fn events_are_one_line_of_key_value_fields() {
+    let position = (4, 7);
+    let line = format_event(Level::Debug, "day6", "guard left the map", &[("position", &position), ("visited", &41)]);
+    assert_eq!(line, "DEBUG day6: guard left the map position=(4, 7) visited=41");
+    assert_eq!(format_event(Level::Info, "runner", "done", &[]), "INFO  runner: done");
+}
+
+#[test]
+fn the_global_filter_gates_events() {
+    // The only test touching the process-wide filter, so nothing races it
+    assert!(!log::enabled(Level::Error, "day6"));
+
+    log::set_filter(Filter::parse("day6=debug").unwrap());
+    assert!(log::enabled(Level::Debug, "day6"));
+    assert!(!log::enabled(Level::Trace, "day6"));
+    assert!(!log::enabled(Level::Error, "day7"));
+
+    let mut formatted = false;
+    let mut field = || {
+        formatted = true;
+        1
+    };
+    event!(Trace, "day6", "not shown", value = field());
+    assert!(!formatted, "a disabled event evaluated its fields");
+
+    log::set_filter(Filter::default());
+    assert!(!log::enabled(Level::Error, "day6"));
+}
@@ -0,0 +1,57 @@
+use aoc_2024_rs::math::{self, Ratio, SolveError};
+
+#[test]
+fn gcd_lcm_and_inverses() {
+    assert_eq!(math::gcd(12i64, -18), Some(6));
+    assert_eq!(math::gcd(0i64, 0), Some(0));
+    assert_eq!(math::gcd(i64::MIN, 2), None);
+    assert_eq!(math::lcm(4i64, 6), Some(12));
+    assert_eq!(math::lcm(i64::MAX, i64::MAX - 1), None);
+
+    let (g, x, y) = math::extended_gcd(240i64, 46).unwrap();
+    assert_eq!(g, 2);
+    assert_eq!(240 * x + 46 * y, 2);
+
+    assert_eq!(math::mod_inverse(3i64, 11), Some(4));
+    assert_eq!(math::mod_inverse(-3i64, 11), Some(7));
+    assert_eq!(math::mod_inverse(4i64, 8), None);
+}
+
+#[test]
+fn chinese_remainders() {
+    assert_eq!(math::crt(&[(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    // Moduli that share a factor still combine when they agree
+    assert_eq!(math::crt(&[(1i128, 4), (3, 6)]), Some((9, 12)));
+    assert_eq!(math::crt(&[(1i64, 4), (2, 6)]), None);
+}
+
+#[test]
+fn nonnegative_linear_solutions() {
+    // (4, 2) is the only solution of 3x + 5y = 22 with both non-negative
+    let solutions = math::nonnegative_solutions(3i64, 5, 22).unwrap();
+    assert_eq!((solutions.x, solutions.y, solutions.count), (4, 2, 1));
+
+    let solutions = math::nonnegative_solutions(2i64, 4, 12).unwrap();
+    assert_eq!(solutions.nth(0), Some((0, 3)));
+    assert_eq!(solutions.last(), Some((6, 0)));
+    assert_eq!(solutions.count, 4);
+
+    assert_eq!(math::nonnegative_solutions(2i64, 4, 7), None);
+    assert_eq!(math::nonnegative_solutions(5i64, 7, 3), None);
+}
+
+#[test]
+fn exact_linear_solves() {
+    let [x, y] = math::solve_2x2([[94i64, 22], [34, 67]], [8400, 5400]).unwrap();
+    assert_eq!((x.to_integer(), y.to_integer()), (Some(80), Some(40)));
+
+    let [x, _] = math::solve_2x2([[2i64, 0], [0, 1]], [1, 1]).unwrap();
+    assert_eq!(x, Ratio::new(1, 2).unwrap());
+    assert_eq!(x.to_string(), "1/2");
+    assert_eq!(math::solve_2x2([[1i64, 2], [2, 4]], [3, 6]), Err(SolveError::Singular));
+
+    let m = vec![vec![2i64, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+    assert_eq!(math::solve_integer(&m, &[8, -11, -3]), Ok(vec![2, 3, -1]));
+    assert_eq!(math::solve_integer(&[vec![2i64]], &[1]), Err(SolveError::NotIntegral));
+    assert_eq!(math::solve_integer(&[vec![i64::MAX, 1], vec![1, i64::MAX]], &[i64::MAX, 3]), Err(SolveError::Overflow));
+}
@@ -0,0 +1,77 @@
+use aoc_2024_rs::parser::{Cursor, Parser};
+use aoc_2024_rs::AocError;
+
+fn position(err: AocError) -> (usize, usize) {
+    match err {
+        AocError::Parse { line, column, .. } => (line, column),
+        other => panic!("expected a parse error, got {}", other),
+    }
+}
+
+#[test]
+fn nested_errors_point_into_the_whole_input() {
+    let input = "1,2\n3,x4\n";
+    let p = Parser::new(0, input);
+    let err = p.lines(input, |line| p.list(line, ",", |n| p.integer::<u32>(n))).unwrap_err();
+    assert_eq!(position(err), (2, 3));
+
+    let digits = "12a4";
+    let err = Parser::new(0, digits).digits(digits).unwrap_err();
+    assert_eq!(position(err), (1, 3));
+}
+
+#[test]
+fn sections_split_on_blank_lines() {
+    let input = "a\nb\n\n\nc\r\n\nd\n";
+    let p = Parser::new(0, input);
+    assert_eq!(p.sections(input), ["a\nb", "c", "d"]);
+    assert!(p.sections("\n\n").is_empty());
+
+    let [first, second] = p.sections_exact::<2>("x\n\ny\n", "two sections").unwrap();
+    assert_eq!((first, second), ("x", "y"));
+
+    let err = p.sections_exact::<2>(input, "two sections").unwrap_err();
+    assert_eq!(position(err), (7, 1));
+}
+
+#[test]
+fn key_values_need_the_key_and_separator() {
+    let input = "X+42, Y=18849, Z-1";
+    let p = Parser::new(0, input);
+    let (x, rest) = p.split_once(input, ",", "a pair").unwrap();
+    let (y, z) = p.split_once(rest, ",", "a pair").unwrap();
+    assert_eq!(p.key_value::<i64>(x, "X", '+').unwrap(), 42);
+    assert_eq!(p.key_value::<i64>(y, "Y", '=').unwrap(), 18849);
+    assert!(p.key_value::<i64>(y, "Y", '+').is_err());
+    assert!(p.key_value::<i64>(z, "X", '-').is_err());
+
+    assert_eq!(p.strip_prefix(input, "X+").unwrap(), &input[2..]);
+    assert_eq!(position(p.strip_prefix(rest, "Y").unwrap_err()), (1, 6));
+}
+
+#[test]
+fn grids_are_checked_against_the_whole_input() {
+    let input = "header\n\n.#\n#x\n";
+    let p = Parser::new(0, input);
+    let [_, body] = p.sections_exact::<2>(input, "a header and a grid").unwrap();
+    let err = p.grid(body, |c| matches!(c, '.' | '#').then_some(c)).unwrap_err();
+    assert_eq!(position(err), (4, 2));
+
+    let grid = p.grid(body, Some).unwrap();
+    assert_eq!((grid.width(), grid.height()), (2, 2));
+}
+
+#[test]
+fn cursor_reads_literals_and_bounded_digits() {
+    let mut cursor = Cursor::new("mul(1234,5)");
+    assert!(!cursor.eat("do"));
+    assert!(cursor.eat("mul("));
+    assert_eq!(cursor.digits(3), Some("123"));
+    assert_eq!(cursor.rest(), "4,5)");
+    cursor.skip();
+    assert_eq!(cursor.digits(3), None);
+    assert!(cursor.eat(","));
+    assert_eq!(cursor.digits(3), Some("5"));
+    cursor.skip();
+    assert!(cursor.is_empty());
+}
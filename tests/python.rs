@@ -0,0 +1,81 @@
+//! Runs with `cargo test --features python`, embedding the interpreter the
+//! extension module would be loaded into.
+#![cfg(feature = "python")]
+
+use aoc_2024_rs::python::aoc_2024_rs;
+use pyo3::prelude::*;
+
+#[test]
+fn the_module_parses_and_solves_examples_from_python() {
+    // Process-wide: the module has to be registered before the interpreter
+    // starts, and the interpreter only starts once
+    pyo3::append_to_inittab!(aoc_2024_rs);
+    Python::initialize();
+
+    Python::attach(|py| {
+        py.run(
+            cr#"
+import aoc_2024_rs
+from aoc_2024_rs import day1, day3, day7, day9, day11, day12, day13
+from aoc_2024_rs.day13 import Machine
+
+def example(day):
+    with open(f"fixtures/day{day}.txt") as f:
+        return f.read()
+
+assert aoc_2024_rs.days()[:3] == [1, 2, 3]
+assert aoc_2024_rs.solve(1, 1, example(1)) == 11
+assert aoc_2024_rs.solve(11, 2, example(11)) is None
+
+left, right = day1.parse_input(example(1))
+assert left[:2] == [3, 4]
+assert (day1.solve_part1(left, right), day1.solve_part2(left, right)) == (11, 31)
+
+instructions = day3.parse_input(example(3))
+assert ("mul", 2, 4) in instructions and ("don't",) in instructions
+assert day3.solve_part2(instructions) == 48
+
+equations = day7.parse_input(example(7))
+assert equations[0] == (190, [10, 19])
+assert day7.solve_part2(equations, part2_operators=["+", "*"]) == 3749
+
+disk = day9.Disk(day9.parse_input("12345"))
+assert disk.blocks[:4] == [0, None, None, 1]
+disk.compact()
+assert disk.blocks[:4] == [0, 2, 2, 1]
+assert disk.checksum() == 60
+
+assert day11.solve_part1(day11.parse_input("125 17"), blinks=25) == 55312
+
+field = day12.parse_input(example(12))
+regions = field.regions()
+assert sum(region.price() for region in regions) == day12.solve_part1(field) == 1930
+r = next(region for region in regions if region.plant == "I" and region.area == 14)
+assert (r.perimeter, r.sides) == (22, 16)
+
+machines = day13.parse_input(example(13))
+assert machines[0].button_a == (94, 34) and machines[0].prize == (8400, 5400)
+assert day13.solve_part1(machines) == 480
+assert day13.solve_part1([Machine((1, 0), (0, 1), (2, 3))], a_cost=1) == 5
+machines[0].prize = (1, 1)
+assert day13.solve_part1(machines[:1]) == 0
+
+try:
+    day1.parse_input("3   4\n4   x3\n")
+    raise AssertionError("expected AocError")
+except aoc_2024_rs.AocError as err:
+    assert str(err) == "day 1, line 2, column 5: invalid number: `x3`", err
+
+try:
+    day11.solve_part1([1], blink=3)
+    raise AssertionError("expected ValueError")
+except ValueError as err:
+    assert str(err) == "[day11] blink: unknown key", err
+"#,
+            None,
+            None,
+        )
+        .map_err(|err| err.display(py))
+        .unwrap();
+    });
+}
@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use aoc_2024_rs::report::{self, Format, Record};
+use aoc_2024_rs::Answer;
+
+fn sample() -> Vec<Record> {
+    vec![
+        Record {
+            day: 7,
+            part: 1,
+            input: "default".to_string(),
+            answer: Some(Answer::Number(3749)),
+            duration: Duration::from_micros(12),
+            error: None,
+        },
+        Record {
+            day: 7,
+            part: 2,
+            input: "default".to_string(),
+            answer: None,
+            duration: Duration::ZERO,
+            error: Some("day 7: bad \"line\", really".to_string()),
+        },
+    ]
+}
+
+#[test]
+fn json_records_escape_strings() {
+    let json = report::render(Format::Json, &sample());
+    assert_eq!(
+        json,
+        "[\n  {\"day\": 7, \"part\": 1, \"input\": \"default\", \"answer\": 3749, \"duration_ns\": 12000, \"error\": null},\n  \
+         {\"day\": 7, \"part\": 2, \"input\": \"default\", \"answer\": null, \"duration_ns\": 0, \"error\": \"day 7: bad \\\"line\\\", really\"}\n]\n"
+    );
+}
+
+#[test]
+fn csv_and_markdown_have_one_row_per_part() {
+    let csv = report::render(Format::Csv, &sample());
+    assert_eq!(
+        csv,
+        "day,part,input,answer,duration_ns,error\n7,1,default,3749,12000,\n7,2,default,,0,\"day 7: bad \"\"line\"\", really\"\n"
+    );
+
+    let markdown = report::render(Format::Markdown, &sample());
+    assert_eq!(markdown.lines().count(), 4);
+    assert!(markdown.contains("| 7 | 1 | default | 3749 | 12.0 µs |  |"), "{}", markdown);
+}
+
+#[test]
+fn formats_parse_by_name() {
+    assert_eq!("json".parse(), Ok(Format::Json));
+    assert_eq!("md".parse(), Ok(Format::Markdown));
+    assert!("xml".parse::<Format>().is_err());
+}
@@ -0,0 +1,132 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use aoc_2024_rs::cancel::Budget;
+use aoc_2024_rs::results::{self, ResultCache};
+use aoc_2024_rs::runner::{self, Job, Status};
+use aoc_2024_rs::solution;
+use aoc_2024_rs::{Answer, Result, Solution};
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc_results_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn the_input_hash_is_fnv_1a() {
+    assert_eq!(results::hash(b""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(results::hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    assert_ne!(results::hash(b"125 17"), results::hash(b"125 18"));
+}
+
+#[test]
+fn answers_are_keyed_by_day_part_input_and_version() {
+    let dir = scratch_dir("keys");
+    let cache = ResultCache::new(&dir);
+    cache.put(6, 1, "input", "1", &Answer::Number(41)).unwrap();
+    cache.put(6, 2, "input", "1", &Answer::Text("abc".to_string())).unwrap();
+
+    assert_eq!(cache.get(6, 1, "input", "1"), Some(Answer::Number(41)));
+    assert_eq!(cache.get(6, 2, "input", "1"), Some(Answer::Text("abc".to_string())));
+    assert_eq!(cache.get(7, 1, "input", "1"), None);
+    assert_eq!(cache.get(6, 1, "input2", "1"), None);
+    assert_eq!(cache.get(6, 1, "input", "2"), None);
+
+    // Stubs are not remembered, and damaged entries are misses
+    cache.put(6, 1, "other", "1", &Answer::Unsolved).unwrap();
+    assert_eq!(cache.get(6, 1, "other", "1"), None);
+    fs::write(cache.path(6, 1, "input", "1"), "number\nforty-one\n").unwrap();
+    assert_eq!(cache.get(6, 1, "input", "1"), None);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn answers_beyond_i64_are_kept_exactly() {
+    let answer = Answer::from(u64::MAX);
+    assert_eq!(answer.to_string(), "18446744073709551615");
+
+    let dir = scratch_dir("wide");
+    let cache = ResultCache::new(&dir);
+    cache.put(11, 1, "input", "1", &answer).unwrap();
+    assert_eq!(cache.get(11, 1, "input", "1"), Some(answer));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn clearing_removes_one_day_or_everything() {
+    let dir = scratch_dir("clear");
+    let cache = ResultCache::new(&dir);
+    assert_eq!(cache.clear(None).unwrap(), 0);
+    for day in [6, 9] {
+        for part in [1, 2] {
+            cache.put(day, part, "input", "1", &Answer::Number(1)).unwrap();
+        }
+    }
+
+    assert_eq!(cache.clear(Some(6)).unwrap(), 2);
+    assert_eq!(cache.get(6, 1, "input", "1"), None);
+    assert_eq!(cache.get(9, 1, "input", "1"), Some(Answer::Number(1)));
+    assert_eq!(cache.clear(None).unwrap(), 2);
+    assert_eq!(cache.get(9, 1, "input", "1"), None);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn the_version_covers_the_solvers_source() {
+    let solver = Counting;
+    let day1 = solution::cache_version(1, &solver);
+    assert!(day1.starts_with("1 "));
+    assert_eq!(day1, solution::cache_version(1, &solver));
+    // Each day hashes its own module, so the same solver files differently
+    assert_ne!(day1, solution::cache_version(2, &solver));
+}
+
+static SOLVED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default)]
+struct Counting;
+
+impl Solution for Counting {
+    fn parse(&mut self, _input: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        SOLVED.fetch_add(1, Ordering::SeqCst);
+        Ok(Answer::Number(7))
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        SOLVED.fetch_add(1, Ordering::SeqCst);
+        Ok(Answer::Unsolved)
+    }
+}
+
+fn counting() -> Box<dyn Solution> {
+    Box::new(Counting)
+}
+
+#[test]
+fn the_runner_answers_from_the_cache() {
+    let dir = scratch_dir("runner");
+    let job = |cache: Option<&ResultCache>| Job { day: 3, new: counting, input: Ok("x".to_string()), cache: cache.cloned() };
+    let cache = ResultCache::new(&dir);
+
+    let first = runner::run_job(job(Some(&cache)), &Budget::unlimited());
+    assert_eq!((first.part1_cached, first.part2_cached), (false, false));
+    assert_eq!(SOLVED.load(Ordering::SeqCst), 2);
+
+    // Part 1 comes from the cache; the unsolved part 2 still runs
+    let second = runner::run_job(job(Some(&cache)), &Budget::unlimited());
+    assert_eq!(second.status, Status::Ok);
+    assert_eq!((second.part1, second.part2), (Some(Answer::Number(7)), Some(Answer::Unsolved)));
+    assert_eq!((second.part1_cached, second.part2_cached), (true, false));
+    assert_eq!(SOLVED.load(Ordering::SeqCst), 3);
+
+    let uncached = runner::run_job(job(None), &Budget::unlimited());
+    assert!(!uncached.part1_cached);
+    assert_eq!(SOLVED.load(Ordering::SeqCst), 5);
+    fs::remove_dir_all(&dir).unwrap();
+}
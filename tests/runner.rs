@@ -0,0 +1,186 @@
+use std::fs::read_to_string;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use aoc_2024_rs::cancel::{self, Budget};
+use aoc_2024_rs::runner::{self, DayRun, Job, RunOptions, Status};
+use aoc_2024_rs::solution::DAYS;
+use aoc_2024_rs::verify::Answers;
+use aoc_2024_rs::{AocError, Answer, Result, Solution};
+
+fn fixture(day: u8) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("fixtures/day{}.txt", day));
+    read_to_string(path).unwrap()
+}
+
+#[test]
+fn parallel_run_keeps_day_order_and_answers() {
+    let answers = Answers::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/answers.toml")).unwrap();
+    // Reversed, so the results can only come back in order by design
+    let jobs = DAYS
+        .iter()
+        .rev()
+        .map(|entry| Job {
+            day: entry.day,
+            new: entry.new,
+            input: Ok(fixture(entry.day)),
+            cache: None,
+        })
+        .collect();
+
+    let runs = runner::run_parallel(jobs, RunOptions { jobs: 4, timeout: None });
+
+    let days: Vec<u8> = runs.iter().map(|run| run.day).collect();
+    let expected_days: Vec<u8> = DAYS.iter().rev().map(|entry| entry.day).collect();
+    assert_eq!(days, expected_days);
+    for run in &runs {
+        assert_eq!(run.status, Status::Ok, "day {}", run.day);
+        let part1 = run.part1.as_ref().unwrap().to_string();
+        assert_eq!(Some(part1), answers.expected("example", run.day, 1), "day {}", run.day);
+    }
+}
+
+#[derive(Default)]
+struct Slow;
+
+impl Solution for Slow {
+    fn parse(&mut self, _input: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        thread::sleep(Duration::from_secs(5));
+        Ok(Answer::Unsolved)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        Ok(Answer::Unsolved)
+    }
+}
+
+fn slow() -> Box<dyn Solution> {
+    Box::new(Slow)
+}
+
+#[test]
+fn slow_days_time_out_and_bad_inputs_error() {
+    let jobs = vec![
+        Job { day: 1, new: slow, input: Ok(String::new()), cache: None },
+        Job { day: 1, new: DAYS[0].new, input: Ok("1 x\n".to_string()), cache: None },
+        Job { day: 2, new: DAYS[1].new, input: Err("missing".to_string()), cache: None },
+        Job { day: 1, new: DAYS[0].new, input: Ok(fixture(1)), cache: None },
+    ];
+
+    // One worker, so the later days only get to run because the stuck one is replaced
+    let runs = runner::run_parallel(jobs, RunOptions { jobs: 1, timeout: Some(Duration::from_millis(200)) });
+
+    assert_eq!(runs[0].status, Status::Timeout);
+    assert!(matches!(runs[1].status, Status::Error(_)));
+    assert_eq!(runs[2].status, Status::Error("missing".to_string()));
+    assert_eq!(runs[3].status, Status::Ok);
+    assert_eq!(runs[3].part1, Some(Answer::Number(11)));
+}
+
+// Spins until its budget runs out, as a long search loop would
+#[derive(Default)]
+struct Cooperative;
+
+impl Solution for Cooperative {
+    fn parse(&mut self, _input: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        loop {
+            cancel::check(1)?;
+        }
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        self.part1()
+    }
+}
+
+fn cooperative() -> Box<dyn Solution> {
+    Box::new(Cooperative)
+}
+
+#[test]
+fn solvers_that_check_their_budget_stop_with_a_timeout() {
+    let job = Job { day: 1, new: cooperative, input: Ok(String::new()), cache: None };
+    let run = runner::run_job(job, &Budget::new(Some(Duration::from_millis(50))));
+    assert_eq!(run.status, Status::Timeout);
+    assert!(run.part1_time < Duration::from_secs(5));
+
+    let day7 = DAYS.iter().find(|d| d.day == 7).unwrap().new;
+    let job = Job { day: 7, new: day7, input: Ok(fixture(7)), cache: None };
+    let budget = Budget::unlimited();
+    budget.cancel();
+    assert_eq!(runner::run_job(job, &budget).status, Status::Timeout);
+}
+
+#[derive(Default)]
+struct Panicky;
+
+impl Solution for Panicky {
+    fn parse(&mut self, _input: &str) -> Result<()> {
+        panic!("the parser gave up");
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        Ok(Answer::Unsolved)
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        Ok(Answer::Unsolved)
+    }
+}
+
+fn panicky() -> Box<dyn Solution> {
+    Box::new(Panicky)
+}
+
+#[test]
+fn a_panicking_solver_fails_its_day_without_stalling_the_rest() {
+    let jobs = vec![
+        Job { day: 1, new: panicky, input: Ok(String::new()), cache: None },
+        Job { day: 1, new: DAYS[0].new, input: Ok(fixture(1)), cache: None },
+    ];
+
+    // One worker and no timeout, so nothing would rescue a lost result
+    let runs = runner::run_parallel(jobs, RunOptions { jobs: 1, timeout: None });
+
+    assert_eq!(runs[0].status, Status::Error(AocError::puzzle(1, "parsing panicked").to_string()));
+    assert_eq!(runs[1].status, Status::Ok);
+    assert_eq!(runs[1].part1, Some(Answer::Number(11)));
+}
+
+#[test]
+fn the_table_lines_up_and_lists_errors_last() {
+    let ok = DayRun {
+        day: 1,
+        part1: Some(Answer::Number(11)),
+        part2: Some(Answer::Number(31)),
+        parse_time: Duration::from_micros(5),
+        part1_time: Duration::from_micros(7),
+        part2_time: Duration::ZERO,
+        part1_cached: false,
+        part2_cached: true,
+        status: Status::Ok,
+    };
+    let failed = DayRun {
+        day: 13,
+        part1: None,
+        part2: None,
+        status: Status::Error("the input is empty".to_string()),
+        ..ok.clone()
+    };
+
+    let table = runner::render(&[ok, failed]);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines[0], "Day  Part 1  Part 2   Parse  Part 1 time  Part 2 time  Status");
+    assert_eq!(lines[1], "1        11      31  5.0 µs       7.0 µs       cached  ok");
+    assert!(lines[2].starts_with("13        -       -"), "{}", lines[2]);
+    assert_eq!(&lines[3..], ["", "day 13: the input is empty"]);
+}
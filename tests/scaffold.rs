@@ -0,0 +1,62 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use aoc_2024_rs::scaffold;
+
+// A scratch copy of the files `new` edits, taken from this crate
+fn scratch_crate(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("aoc_scaffold_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for file in ["src/lib.rs", "src/solution.rs", "tests/examples.rs"] {
+        fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+        fs::copy(manifest.join(file), root.join(file)).unwrap();
+    }
+    root
+}
+
+#[test]
+fn new_day_is_written_and_registered_in_order() {
+    let root = scratch_crate("order");
+    scaffold::scaffold(&root, 20).unwrap();
+    let changed = scaffold::scaffold(&root, 14).unwrap();
+    assert!(changed.contains(&root.join("src/day14.rs")));
+    assert!(changed.contains(&root.join("fixtures/day14.txt")));
+
+    let module = fs::read_to_string(root.join("src/day14.rs")).unwrap();
+    assert!(module.contains("impl Solution for Day14"));
+    assert!(module.contains("const DAY: u8 = 14;"));
+    assert_eq!(fs::read_to_string(root.join("inputs/day14.txt")).unwrap(), "");
+
+    let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+    let mods: Vec<&str> = lib.lines().filter(|line| line.starts_with("pub mod day")).collect();
+    assert_eq!(mods[mods.len() - 3..], ["pub mod day13;", "pub mod day14;", "pub mod day20;"]);
+
+    let registry = fs::read_to_string(root.join("src/solution.rs")).unwrap();
+    let day13 = registry.find("<day13::Day13>").unwrap();
+    let day14 = registry.find("boxed::<day14::Day14>").unwrap();
+    let day20 = registry.find("boxed::<day20::Day20>").unwrap();
+    assert!(day13 < day14 && day14 < day20);
+    assert!(registry.contains(" day14, ") && registry.contains(" day20, "));
+
+    let tests = fs::read_to_string(root.join("tests/examples.rs")).unwrap();
+    assert!(tests.contains("fn day14()") && tests.contains("fn day20()"));
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn existing_and_impossible_days_are_refused() {
+    let root = scratch_crate("refused");
+    scaffold::scaffold(&root, 14).unwrap();
+    let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+
+    let err = scaffold::scaffold(&root, 14).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    assert!(scaffold::scaffold(&root, 0).is_err());
+    assert!(scaffold::scaffold(&root, 26).is_err());
+    assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), lib);
+
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,56 @@
+use aoc_2024_rs::search::{self, Cycle};
+
+// 0 -> 1 -> 3, 0 -> 2 -> 3, 3 -> 4; 5 is unreachable
+fn neighbours(&n: &u32) -> Vec<u32> {
+    match n {
+        0 => vec![1, 2],
+        1 | 2 => vec![3],
+        3 => vec![4],
+        _ => vec![],
+    }
+}
+
+#[test]
+fn bfs_and_dfs_reach_the_same_nodes() {
+    let bfs = search::bfs([0], neighbours);
+    assert_eq!(bfs.order(), [0, 1, 2, 3, 4]);
+    assert_eq!(bfs.cost(&4), Some(3));
+    assert_eq!(bfs.path_to(&4), Some(vec![0, 1, 3, 4]));
+    assert_eq!(bfs.path_to(&5), None);
+
+    let dfs = search::dfs(0, neighbours);
+    assert_eq!(dfs.order(), [0, 1, 3, 4, 2]);
+
+    assert_eq!(search::flood_fill(2, neighbours), [2, 3, 4]);
+    assert_eq!(search::count_paths(0, neighbours, |&n| n == 4), 2);
+}
+
+#[test]
+fn weighted_searches_find_the_cheapest_route() {
+    // The direct edge 0 -> 2 costs more than going round through 1
+    let edges = |&n: &u32| -> Vec<(u32, u32)> {
+        match n {
+            0 => vec![(1, 1), (2, 10)],
+            1 => vec![(2, 2)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    };
+
+    let reached = search::dijkstra(0, edges);
+    assert_eq!(reached.cost(&3), Some(4));
+    assert_eq!(reached.path_to(&3), Some(vec![0, 1, 2, 3]));
+
+    let found = search::astar(0, edges, |&n| 3 - n.min(3), |&n| n == 3);
+    assert_eq!(found, Some((vec![0, 1, 2, 3], 4)));
+    assert_eq!(search::astar(0, edges, |_| 0, |&n| n == 9), None);
+}
+
+#[test]
+fn topological_sort_orders_or_reports_the_cycle() {
+    let sorted = search::topological_sort([4, 3, 2, 1], [(1, 2), (2, 3), (1, 4), (9, 1)]);
+    assert_eq!(sorted, Ok(vec![1, 2, 4, 3]));
+
+    let cycle = search::topological_sort([1, 2, 3, 4], [(1, 2), (2, 3), (3, 2), (3, 4)]);
+    assert_eq!(cycle, Err(Cycle { nodes: vec![2, 3, 4] }));
+}
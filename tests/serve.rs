@@ -0,0 +1,104 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+use aoc_2024_rs::results::ResultCache;
+use aoc_2024_rs::serve::{self, Request, ServeOptions};
+
+const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+/// Starts a server on a free loopback port and returns its address.
+fn start(options: ServeOptions) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    thread::spawn(move || serve::serve(listener, options));
+    address
+}
+
+/// Sends a raw request and returns the status code and body.
+fn send(address: &str, request: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response.split_whitespace().nth(1).unwrap().parse().unwrap();
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    (status, body.to_string())
+}
+
+fn post(address: &str, path: &str, body: &str) -> (u16, String) {
+    send(address, &format!("POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}", path, body.len(), body))
+}
+
+#[test]
+fn posting_an_input_returns_the_answer_as_json() {
+    let address = start(ServeOptions::default());
+
+    let (status, body) = post(&address, "/day/1/part/1", EXAMPLE);
+    assert_eq!(status, 200, "{}", body);
+    assert!(body.starts_with("{\"day\": 1, \"part\": 1, \"answer\": 11, \"parse_ns\": "), "{}", body);
+    assert!(body.ends_with("\"cached\": false}"), "{}", body);
+
+    let (status, body) = post(&address, "/day/1/part/2?verbose=1", EXAMPLE);
+    assert_eq!(status, 200);
+    assert!(body.contains("\"answer\": 31"), "{}", body);
+
+    let (status, body) = send(&address, "GET /days HTTP/1.1\r\n\r\n");
+    assert_eq!(status, 200);
+    assert!(body.starts_with("{\"days\": [1, 2, 3,"), "{}", body);
+}
+
+#[test]
+fn failures_come_back_as_json_errors() {
+    let address = start(ServeOptions::default());
+
+    let (status, body) = post(&address, "/day/1/part/1", "3   4\n4   x3\n");
+    assert_eq!(status, 422);
+    assert_eq!(body, "{\"error\": \"day 1, line 2, column 5: invalid number: `x3`\"}");
+
+    assert_eq!(post(&address, "/day/1/part/1", "").0, 400);
+    assert_eq!(post(&address, "/day/1/part/3", EXAMPLE).0, 404);
+    assert_eq!(post(&address, "/day/26/part/1", EXAMPLE).0, 404);
+    assert_eq!(post(&address, "/day/x/part/1", EXAMPLE).0, 404);
+    assert_eq!(post(&address, "/solve", EXAMPLE).0, 404);
+    assert_eq!(send(&address, "GET /day/1/part/1 HTTP/1.1\r\n\r\n").0, 405);
+    assert_eq!(send(&address, "nonsense\r\n\r\n").0, 400);
+
+    let chunked = "POST /day/1/part/1 HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n3\r\n1 2\r\n0\r\n\r\n";
+    assert_eq!(send(&address, chunked).0, 411);
+}
+
+#[test]
+fn clients_that_expect_100_continue_are_told_to_send_the_body() {
+    let address = start(ServeOptions::default());
+    let mut stream = TcpStream::connect(&address).unwrap();
+    write!(stream, "POST /day/1/part/1 HTTP/1.1\r\nContent-Length: {}\r\nExpect: 100-continue\r\n\r\n", EXAMPLE.len()).unwrap();
+
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(line, "HTTP/1.1 100 Continue\r\n");
+    reader.read_line(&mut line).unwrap();
+
+    stream.write_all(EXAMPLE.as_bytes()).unwrap();
+    let mut response = String::new();
+    reader.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+    assert!(response.contains("\"answer\": 11"), "{}", response);
+}
+
+#[test]
+fn answers_are_cached_between_requests() {
+    let dir = std::env::temp_dir().join(format!("aoc_serve_cache_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let options = ServeOptions { timeout: None, cache: Some(ResultCache::new(&dir)) };
+
+    let request = Request { method: "POST".to_string(), path: "/day/1/part/1".to_string(), body: EXAMPLE.as_bytes().to_vec() };
+    let first = serve::handle(&request, &options);
+    assert!(first.body.contains("\"cached\": false"), "{}", first.body);
+    let second = serve::handle(&request, &options);
+    assert_eq!(second.status, 200);
+    assert_eq!(second.body, "{\"day\": 1, \"part\": 1, \"answer\": 11, \"parse_ns\": 0, \"solve_ns\": 0, \"cached\": true}");
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,96 @@
+use std::fs;
+use std::ops::ControlFlow;
+use std::thread;
+use std::time::Duration;
+
+use aoc_2024_rs::input::InputSource;
+use aoc_2024_rs::runner::{DayRun, Status};
+use aoc_2024_rs::solution::{self, Answer};
+use aoc_2024_rs::watch::{self, Change, FileWatcher};
+
+fn run(part1: Option<i128>, part2: Option<i128>) -> DayRun {
+    DayRun {
+        day: 12,
+        part1: part1.map(Answer::Number),
+        part2: part2.map(Answer::Number),
+        parse_time: Duration::from_micros(5),
+        part1_time: Duration::from_micros(7),
+        part2_time: Duration::from_micros(9),
+        part1_cached: false,
+        part2_cached: false,
+        status: Status::Ok,
+    }
+}
+
+#[test]
+fn only_changed_parts_are_reported() {
+    assert!(watch::changes(&run(Some(1), Some(2)), &run(Some(1), Some(2))).is_empty());
+    assert_eq!(
+        watch::changes(&run(Some(1), Some(2)), &run(Some(1), None)),
+        [Change {
+            part: 2,
+            old: "2".to_string(),
+            new: "-".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn changed_answers_are_shown_side_by_side() {
+    let first = watch::render(&run(Some(1930), Some(1206)), None);
+    assert_eq!(first, "day 12: ok, parsed in 5.0 µs\nPart 1  1930  7.0 µs\nPart 2  1206  9.0 µs\n");
+
+    let same = watch::render(&run(Some(1930), Some(1206)), Some(&run(Some(1930), Some(1206))));
+    assert_eq!(same, first);
+
+    let moved = watch::render(&run(Some(140), Some(1206)), Some(&run(Some(1930), Some(1206))));
+    let lines: Vec<&str> = moved.lines().collect();
+    assert_eq!(lines[1..], ["         old   new", "Part 1  1930   140  7.0 µs  changed", "Part 2  1206  1206  9.0 µs"]);
+}
+
+#[test]
+fn file_watcher_sees_edits_and_deletion() {
+    let path = std::env::temp_dir().join(format!("aoc_watch_poll_{}.txt", std::process::id()));
+    fs::write(&path, "a").unwrap();
+    let mut watcher = FileWatcher::new(&path);
+    assert!(watcher.poll());
+    assert!(!watcher.poll());
+
+    fs::write(&path, "ab").unwrap();
+    assert!(watcher.poll());
+    assert!(!watcher.poll());
+
+    fs::remove_file(&path).unwrap();
+    assert!(watcher.poll());
+    assert!(!watcher.poll());
+}
+
+#[test]
+fn watch_reruns_after_each_change() {
+    let path = std::env::temp_dir().join(format!("aoc_watch_run_{}.txt", std::process::id()));
+    let example = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/day12.txt")).unwrap();
+    fs::write(&path, &example).unwrap();
+
+    let writer = {
+        let path = path.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(200));
+            fs::write(&path, "AAAA\nBBCD\nBBCC\nEEEC\n").unwrap();
+        })
+    };
+
+    let new = solution::DAYS.iter().find(|d| d.day == 12).unwrap().new;
+    let mut seen = Vec::new();
+    let source = InputSource::Path(path.clone());
+    watch::watch(12, new, &source, FileWatcher::new(&path), Duration::from_millis(20), None, |run, previous| {
+        seen.push((run.part1.clone(), previous.map(|p| watch::changes(p, run))));
+        if seen.len() == 2 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+    });
+    writer.join().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(seen[0], (Some(Answer::Number(1930)), None));
+    let (part1, changes) = &seen[1];
+    assert_eq!(part1, &Some(Answer::Number(140)));
+    assert_eq!(changes.as_ref().unwrap().len(), 2);
+}